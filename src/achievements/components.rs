@@ -0,0 +1,11 @@
+use bevy::prelude::Component;
+
+// column in the top right corner the toasts stack in
+#[derive(Component)]
+pub struct ToastRoot;
+
+// notification of an unlocked achievement, gone after a few seconds
+#[derive(Component)]
+pub struct Toast {
+    pub until: f32,
+}
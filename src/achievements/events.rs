@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+#[derive(Event)]
+pub struct AchievementUnlocked {
+    pub name: String,
+    pub description: String,
+}
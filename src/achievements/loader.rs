@@ -0,0 +1,21 @@
+use bevy::utils::HashSet;
+
+use crate::data::RonAsset;
+
+use super::resources::AchievementSet;
+
+impl RonAsset for AchievementSet {
+    const NAME: &'static str = "achievements";
+    const EXTENSIONS: &'static [&'static str] = &["achievements.ron"];
+
+    fn validate(&mut self) -> Result<(), String> {
+        // the saved progress is keyed by id
+        let mut ids = HashSet::new();
+        for achievement in self.achievements.iter() {
+            if !ids.insert(achievement.id.as_str()) {
+                return Err(format!("achievement id {} is used more than once", achievement.id));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+pub mod components;
+pub mod events;
+pub mod loader;
+pub mod resources;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, OnEnter, in_state, not},
+    app::{Startup, Update, PostUpdate, Last},
+};
+
+use crate::{data::RonAssetApp, netplay::systems::netplay_active, replay::systems::replay_playing, systems::AppState};
+
+use self::{
+    events::AchievementUnlocked,
+    resources::{AchievementSet, RunTracker},
+    systems::{
+        load_achievement_set, load_achievement_progress, reset_run_tracker, track_achievements, save_achievement_progress,
+        spawn_toast_root, show_toasts, expire_toasts
+    },
+};
+
+// long-term goals defined in `assets/data/default.achievements.ron`, unlocked by what happens in the runs
+// and kept in the saves. replays and online matches do not count, a rollback would see the same events twice
+pub struct AchievementPlug;
+
+impl Plugin for AchievementPlug {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<AchievementSet>()
+            .init_resource::<RunTracker>()
+            .add_event::<AchievementUnlocked>()
+            .add_systems(Startup, (load_achievement_set, load_achievement_progress, spawn_toast_root))
+            .add_systems(OnEnter(AppState::Playing), reset_run_tracker)
+            .add_systems(PostUpdate, track_achievements
+                .run_if(in_state(AppState::Playing))
+                .run_if(not(replay_playing))
+                .run_if(not(netplay_active)))
+            .add_systems(Update, (show_toasts, expire_toasts))
+            .add_systems(Last, save_achievement_progress);
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use bevy::{prelude::*, reflect::TypePath};
+use serde::{Deserialize, Serialize};
+
+use crate::{modes::resources::GameMode, saves::{load_save, write_save}};
+
+const PROGRESS_SAVE: &str = "achievements";
+
+// what has to happen for an achievement to unlock
+#[derive(Deserialize)]
+pub enum Condition {
+    ReachLevel(u32),
+    // bonuses caught in a row without losing a life
+    BonusStreak(u32),
+    // score of the team in a single run, in a given game mode or any of them
+    Score { score: u32, mode: Option<GameMode> },
+    // bonuses caught over every run
+    TotalBonuses(u32),
+    Runs(u32),
+}
+
+// something seen during a run that may move an achievement forward
+#[derive(Clone, Copy)]
+pub enum Observation {
+    Level(u32),
+    Streak(u32),
+    Score { score: u32, mode: GameMode },
+    Bonus,
+    RunOver,
+}
+
+impl Condition {
+    pub fn target(&self) -> u32 {
+        match *self {
+            Condition::ReachLevel(target)
+            | Condition::BonusStreak(target)
+            | Condition::Score { score: target, .. }
+            | Condition::TotalBonuses(target)
+            | Condition::Runs(target) => target,
+        }
+    }
+
+    // the progress once the observation is taken into account
+    pub fn progress(&self, observation: Observation, progress: u32) -> u32 {
+        match (self, observation) {
+            (Condition::ReachLevel(_), Observation::Level(level)) => progress.max(level),
+            (Condition::BonusStreak(_), Observation::Streak(streak)) => progress.max(streak),
+            (Condition::Score { mode: wanted, .. }, Observation::Score { score, mode })
+                if wanted.is_none_or(|wanted| wanted == mode) => progress.max(score),
+            (Condition::TotalBonuses(_), Observation::Bonus) | (Condition::Runs(_), Observation::RunOver) => progress + 1,
+            _ => progress,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct AchievementDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: Condition,
+}
+
+#[derive(Asset, TypePath, Deserialize)]
+pub struct AchievementSet {
+    pub achievements: Vec<AchievementDefinition>,
+}
+
+#[derive(Resource)]
+pub struct AchievementSetHandle {
+    pub handle: Handle<AchievementSet>,
+}
+
+// unlocks and progress counters of every achievement on this machine, by id
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct AchievementProgress {
+    pub unlocked: BTreeSet<String>,
+    pub counters: BTreeMap<String, u32>,
+    // counters changed since the last save
+    #[serde(skip)]
+    pub dirty: bool,
+}
+
+impl AchievementProgress {
+    pub fn load() -> Self {
+        load_save(PROGRESS_SAVE)
+    }
+
+    pub fn save(&mut self) {
+        write_save(PROGRESS_SAVE, self);
+        self.dirty = false;
+    }
+
+    // moves every achievement forward, returns the ones that just unlocked
+    pub fn observe<'a>(&mut self, achievement_set: &'a AchievementSet, observation: Observation) -> Vec<&'a AchievementDefinition> {
+        let mut unlocked = Vec::new();
+        for achievement in achievement_set.achievements.iter() {
+            if self.unlocked.contains(&achievement.id) {
+                continue;
+            }
+            let counter = self.counters.entry(achievement.id.clone()).or_default();
+            let progress = achievement.condition.progress(observation, *counter);
+            if progress == *counter {
+                continue;
+            }
+            *counter = progress;
+            self.dirty = true;
+            if progress >= achievement.condition.target() {
+                self.unlocked.insert(achievement.id.clone());
+                unlocked.push(achievement);
+            }
+        }
+        unlocked
+    }
+}
+
+// what the current run has done so far
+#[derive(Resource, Default)]
+pub struct RunTracker {
+    pub bonus_streak: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn achievement(id: &str, condition: Condition) -> AchievementDefinition {
+        AchievementDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            condition,
+        }
+    }
+
+    fn unlocked_ids(unlocked: Vec<&AchievementDefinition>) -> Vec<&str> {
+        unlocked.into_iter().map(|achievement| achievement.id.as_str()).collect()
+    }
+
+    #[test]
+    fn highest_values_are_kept() {
+        let condition = Condition::ReachLevel(5);
+        assert_eq!(condition.progress(Observation::Level(3), 1), 3);
+        assert_eq!(condition.progress(Observation::Level(2), 3), 3);
+    }
+
+    #[test]
+    fn counters_go_up_by_one() {
+        assert_eq!(Condition::TotalBonuses(50).progress(Observation::Bonus, 7), 8);
+        assert_eq!(Condition::Runs(10).progress(Observation::RunOver, 0), 1);
+    }
+
+    #[test]
+    fn other_observations_leave_the_progress() {
+        assert_eq!(Condition::ReachLevel(5).progress(Observation::Bonus, 2), 2);
+        assert_eq!(Condition::Runs(10).progress(Observation::Streak(4), 2), 2);
+    }
+
+    #[test]
+    fn scores_only_count_in_the_wanted_mode() {
+        let hardcore = Condition::Score { score: 100, mode: Some(GameMode::Hardcore) };
+        assert_eq!(hardcore.progress(Observation::Score { score: 80, mode: GameMode::Endless }, 0), 0);
+        assert_eq!(hardcore.progress(Observation::Score { score: 80, mode: GameMode::Hardcore }, 0), 80);
+
+        let any = Condition::Score { score: 100, mode: None };
+        assert_eq!(any.progress(Observation::Score { score: 80, mode: GameMode::TimeAttack }, 0), 80);
+    }
+
+    #[test]
+    fn an_achievement_unlocks_once_on_its_target() {
+        let achievement_set = AchievementSet {
+            achievements: vec![achievement("runs", Condition::Runs(2)), achievement("level", Condition::ReachLevel(3))],
+        };
+        let mut progress = AchievementProgress::default();
+
+        assert!(progress.observe(&achievement_set, Observation::RunOver).is_empty());
+        assert!(progress.dirty);
+        assert_eq!(unlocked_ids(progress.observe(&achievement_set, Observation::RunOver)), vec!["runs"]);
+        assert!(progress.observe(&achievement_set, Observation::RunOver).is_empty());
+        assert_eq!(progress.counters["runs"], 2);
+    }
+
+    #[test]
+    fn observing_nothing_new_keeps_the_progress_clean() {
+        let achievement_set = AchievementSet { achievements: vec![achievement("level", Condition::ReachLevel(3))] };
+        let mut progress = AchievementProgress::default();
+        progress.observe(&achievement_set, Observation::Bonus);
+        assert!(!progress.dirty);
+
+        assert_eq!(unlocked_ids(progress.observe(&achievement_set, Observation::Level(4))), vec!["level"]);
+        assert!(progress.unlocked.contains("level"));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    background::{events::LevelUpEvent, resources::GameMetadata},
+    player::{
+        events::{EarnPointEvent, LoseLifeEvent, PointSource},
+        resources::MatchSettings,
+    },
+    systems::GameOver,
+};
+
+use super::{
+    components::{Toast, ToastRoot},
+    events::AchievementUnlocked,
+    resources::{AchievementProgress, AchievementSet, AchievementSetHandle, Observation, RunTracker},
+};
+
+use bevy::{app::AppExit, prelude::*};
+
+const TOAST_DURATION: f32 = 4.;
+const TOAST_NAME_FONT_SIZE: f32 = 24.;
+const TOAST_DESCRIPTION_FONT_SIZE: f32 = 16.;
+const TOAST_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+
+pub fn load_achievement_set(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let handle: Handle<AchievementSet> = asset_server.load("data/default.achievements.ron");
+    commands.insert_resource(AchievementSetHandle { handle });
+}
+
+pub fn load_achievement_progress(mut commands: Commands) {
+    commands.insert_resource(AchievementProgress::load());
+}
+
+pub fn reset_run_tracker(mut run_tracker: ResMut<RunTracker>) {
+    *run_tracker = RunTracker::default();
+}
+
+// once every tick of the frame is simulated, whatever happened moves the achievements forward
+#[allow(clippy::too_many_arguments)]
+pub fn track_achievements(
+    mut earn_point_events: EventReader<EarnPointEvent>,
+    mut lose_life_events: EventReader<LoseLifeEvent>,
+    mut level_up_events: EventReader<LevelUpEvent>,
+    mut game_over_events: EventReader<GameOver>,
+    mut unlocked_event: EventWriter<AchievementUnlocked>,
+    mut achievement_progress: ResMut<AchievementProgress>,
+    mut run_tracker: ResMut<RunTracker>,
+    achievement_sets: Res<Assets<AchievementSet>>,
+    achievement_set_handle: Res<AchievementSetHandle>,
+    game_metadata: Res<GameMetadata>,
+    match_settings: Res<MatchSettings>,
+) {
+    let mut observations = Vec::new();
+    let mut scored = false;
+    for event in earn_point_events.read() {
+        scored = true;
+        if event.source == PointSource::Bonus {
+            run_tracker.bonus_streak += 1;
+            observations.push(Observation::Bonus);
+            observations.push(Observation::Streak(run_tracker.bonus_streak));
+        }
+    }
+    if lose_life_events.read().count() > 0 {
+        run_tracker.bonus_streak = 0;
+    }
+    if scored {
+        observations.push(Observation::Score {
+            score: game_metadata.scores(),
+            mode: match_settings.game_mode,
+        });
+    }
+    for event in level_up_events.read() {
+        observations.push(Observation::Level(event.level as u32));
+    }
+    if game_over_events.read().count() > 0 {
+        observations.push(Observation::RunOver);
+    }
+
+    let Some(achievement_set) = achievement_sets.get(&achievement_set_handle.handle) else {
+        return;
+    };
+    let mut any_unlocked = false;
+    for observation in observations {
+        for achievement in achievement_progress.observe(achievement_set, observation) {
+            info!("achievement unlocked: {}", achievement.name);
+            unlocked_event.send(AchievementUnlocked {
+                name: achievement.name.clone(),
+                description: achievement.description.clone(),
+            });
+            any_unlocked = true;
+        }
+    }
+    // an unlock is kept right away, the counters wait for the end of the game
+    if any_unlocked {
+        achievement_progress.save();
+    }
+}
+
+pub fn save_achievement_progress(
+    mut app_exit_event_reader: EventReader<AppExit>,
+    mut achievement_progress: ResMut<AchievementProgress>,
+) {
+    if app_exit_event_reader.read().last().is_some() && achievement_progress.dirty {
+        achievement_progress.save();
+    }
+}
+
+pub fn spawn_toast_root(mut commands: Commands) {
+    commands.spawn((
+        ToastRoot,
+        NodeBundle {
+            z_index: ZIndex::Global(i32::MAX),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(80.),
+                right: Val::Px(24.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                row_gap: Val::Px(8.),
+                ..default()
+            },
+            ..default()
+        },
+    ));
+}
+
+pub fn show_toasts(
+    mut commands: Commands,
+    mut unlocked_events: EventReader<AchievementUnlocked>,
+    root_query: Query<Entity, With<ToastRoot>>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time<Real>>,
+) {
+    let Ok(root) = root_query.get_single() else {
+        return;
+    };
+    for event in unlocked_events.read() {
+        let font = asset_server.load("BungeeSpice-Regular.ttf");
+        let toast = commands.spawn((
+            Toast {
+                until: time.elapsed_seconds() + TOAST_DURATION,
+            },
+            NodeBundle {
+                background_color: BackgroundColor(Color::BLACK.with_a(0.7)),
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                ..default()
+            },
+        )).with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("achievement: {}", event.name),
+                TextStyle {
+                    font_size: TOAST_NAME_FONT_SIZE,
+                    color: TOAST_COLOR,
+                    font: font.clone(),
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                event.description.clone(),
+                TextStyle {
+                    font_size: TOAST_DESCRIPTION_FONT_SIZE,
+                    color: Color::WHITE,
+                    font,
+                },
+            ));
+        }).id();
+        commands.entity(root).add_child(toast);
+    }
+}
+
+pub fn expire_toasts(
+    mut commands: Commands,
+    toast_query: Query<(Entity, &Toast)>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, toast) in toast_query.iter() {
+        if time.elapsed_seconds() >= toast.until {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
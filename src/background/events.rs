@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+#[derive(Event)]
+pub struct LevelUpEvent {
+    pub level: f32,
+}
@@ -1,22 +1,31 @@
 pub mod systems;
-pub mod components;
 pub mod resources;
+pub mod events;
 
 use self::{
     systems::{
-        spawn_grass, spawn_lifes, update_user_life, show_scores, update_points
+        init_game_boundary, init_game_metadata, update_user_life, update_points, level_up
     }, 
-    resources::GameMetadata
+    resources::{GameMetadata, LevelTimer, RunRules},
+    events::LevelUpEvent,
 };
 
-use bevy::{prelude::{Plugin, App}, app::{Startup, Update}};
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, OnEnter}, app::{Startup, FixedUpdate}};
+
+use crate::{collision::CollisionSet, simulation::{SimulationSet, rollback::RollbackApp}, systems::AppState};
 
 pub struct BackgroundPlug;
 
 impl Plugin for BackgroundPlug {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameMetadata>()
-            .add_systems(Startup, (spawn_grass, spawn_lifes, show_scores))
-            .add_systems(Update, (update_user_life, update_points));
+            .init_resource::<LevelTimer>()
+            .init_resource::<RunRules>()
+            .rollback_resource::<GameMetadata>()
+            .rollback_resource::<LevelTimer>()
+            .add_event::<LevelUpEvent>()
+            .add_systems(Startup, init_game_boundary)
+            .add_systems(OnEnter(AppState::Playing), init_game_metadata)
+            .add_systems(FixedUpdate, (update_user_life, update_points, level_up).after(CollisionSet::Reaction).before(SimulationSet::End));
     }
 }
\ No newline at end of file
@@ -1,5 +1,10 @@
 use bevy::prelude::*;
 
+use crate::player::systems::INVULNERABLE_DURATION;
+
+pub const LEVEL_DURATION: f32 = 20.;
+const STARTING_LIFES: u8 = 3;
+
 #[derive(Resource, Clone, Copy)]
 pub struct GameBonudary {
     pub x_min: f32,
@@ -10,19 +15,145 @@ pub struct GameBonudary {
     pub y_mid: f32,
 }
 
-#[derive(Resource)]
-pub struct GameMetadata {
-    pub level: f32,
+#[derive(Clone, Copy)]
+pub struct PlayerStats {
     pub lifes: u8,
     pub scores: u32,
 }
 
+impl Default for PlayerStats {
+    fn default() -> Self {
+        PlayerStats {
+            lifes: STARTING_LIFES,
+            scores: 0,
+        }
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct GameMetadata {
+    pub level: f32,
+    pub players: Vec<PlayerStats>,
+}
+
 impl Default for GameMetadata {
     fn default() -> Self {
+        GameMetadata::new(1)
+    }
+}
+
+impl GameMetadata {
+    pub fn new(players: usize) -> Self {
         GameMetadata {
             level: 1.,
-            lifes: 3,
-            scores: 0,
+            players: vec![PlayerStats::default(); players],
         }
     }
-}
\ No newline at end of file
+
+    pub fn with_rules(players: usize, rules: &RunRules) -> Self {
+        GameMetadata {
+            level: rules.starting_level,
+            players: vec![PlayerStats { lifes: rules.starting_lifes, scores: 0 }; players],
+        }
+    }
+
+    // score of the whole team
+    pub fn scores(&self) -> u32 {
+        self.players.iter().map(|player| player.scores).sum()
+    }
+}
+// how a run plays, set before it starts by the game mode and e.g. the daily challenge
+#[derive(Resource, Clone, Copy)]
+pub struct RunRules {
+    pub starting_lifes: u8,
+    pub starting_level: f32,
+    // fall speed multiplier of the enemies
+    pub enemy_speed: f32,
+    // score multiplier of the bonuses
+    pub bonus_score: u32,
+    // seconds a character cannot be hit again after losing a life
+    pub invulnerable_duration: f32,
+    // seconds between two level ups
+    pub level_duration: f32,
+    // seconds before the run ends whatever happens
+    pub time_limit: Option<f32>,
+    // vertical direction the ufos move to, upward when the gravity is mirrored
+    pub fall_direction: f32,
+    // size multiplier of the ufos, their sprite and their hitbox
+    pub ufo_scale: f32,
+    // size multiplier of the character hitboxes, the sprites are left as they are
+    pub character_hitbox_scale: f32,
+    pub bonuses: bool,
+    // enemies only show up for a moment from time to time
+    pub enemies_hidden: bool,
+    // speed of the whole game
+    pub game_speed: f32,
+    // multiplier of every point earned, higher for harder runs
+    pub score_multiplier: f32,
+}
+
+impl RunRules {
+    pub fn scaled_score(&self, scores: u32) -> u32 {
+        (scores as f32 * self.score_multiplier).round() as u32
+    }
+
+    // where an ufo meant to show up at `translation` starts. with mirrored gravity they start from the bottom,
+    // out of the playfield as the characters stand right there
+    pub fn ufo_start(&self, translation: Vec3, half_height: f32, game_boundary: &GameBonudary) -> Vec3 {
+        if self.fall_direction > 0. {
+            let y = game_boundary.y_min + game_boundary.y_max - translation.y - half_height;
+            Vec3::new(translation.x, y, translation.z)
+        } else {
+            translation
+        }
+    }
+
+    // whether an ufo went past the edge of the playfield it moves towards, a negative speed goes against the gravity
+    pub fn ufo_gone(&self, translation: Vec3, fall_speed: f32, game_boundary: &GameBonudary) -> bool {
+        if self.fall_direction * fall_speed > 0. {
+            translation.y > game_boundary.y_max
+        } else {
+            translation.y < game_boundary.y_min
+        }
+    }
+}
+
+impl Default for RunRules {
+    fn default() -> Self {
+        RunRules {
+            starting_lifes: STARTING_LIFES,
+            starting_level: 1.,
+            enemy_speed: 1.,
+            bonus_score: 1,
+            invulnerable_duration: INVULNERABLE_DURATION,
+            level_duration: LEVEL_DURATION,
+            time_limit: None,
+            fall_direction: -1.,
+            ufo_scale: 1.,
+            character_hitbox_scale: 1.,
+            bonuses: true,
+            enemies_hidden: false,
+            game_speed: 1.,
+            score_multiplier: 1.,
+        }
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct LevelTimer {
+    pub timer: Timer
+}
+
+impl Default for LevelTimer {
+    fn default() -> Self {
+        LevelTimer::new(LEVEL_DURATION)
+    }
+}
+
+impl LevelTimer {
+    pub fn new(duration: f32) -> Self {
+        LevelTimer {
+            timer: Timer::from_seconds(duration, TimerMode::Repeating)
+        }
+    }
+}
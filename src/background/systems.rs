@@ -1,7 +1,9 @@
 use crate::{
     player::{
+        components::Character,
+        resources::{GameOverRule, MatchSettings, MatchMode},
         systems::{
-            CHARACTER_WIDTH, CHARACTER_HEIGHT
+            CHARACTER_WIDTH, PLAYER_TINTS
         },
         events::{LoseLifeEvent, EarnPointEvent}
     }, 
@@ -9,24 +11,14 @@ use crate::{
 };
 
 use super::{
-    components::{
-        Grass, LifeIcon, CurrentScore, CurrentScoreRoot
-    }, 
     resources::{
-        GameBonudary, GameMetadata
-    }
+        GameBonudary, GameMetadata, LevelTimer, RunRules
+    },
+    events::LevelUpEvent,
 };
 
 use bevy::{prelude::*, window::PrimaryWindow};
 
-const OVERLAY_IDX: f32 = 5.;
-
-const GRASS_WIDTH_ORIGINAL: f32 = 51.0;
-const GRASS_HEIGHT_ORIGINAL: f32 = 50.0;
-const GRASS_SCALE: f32 = CHARACTER_WIDTH / GRASS_WIDTH_ORIGINAL;
-const GRASS_WIDTH_SCALED: f32 = GRASS_WIDTH_ORIGINAL * GRASS_SCALE;
-const GRASS_HEIGHT_SCALED: f32 = GRASS_HEIGHT_ORIGINAL * GRASS_SCALE;
-
 // one extra row and col is given. but they are not accessible as it reflects the character size
 pub const WINDOW_WIDTH: f32 = CHARACTER_WIDTH * 7.;
 pub const WINDOW_HEIGHT: f32 = CHARACTER_WIDTH * 9.; 
@@ -44,141 +36,26 @@ fn get_game_boundary(window: &Window) -> GameBonudary {
     }
 }
 
-pub fn spawn_grass(
+pub fn init_game_boundary(
     mut commands: Commands,
     window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
 ) {
     let window = window_query.get_single().unwrap();
-    
+
     // find mid pt of the window
     let game_boundary = get_game_boundary(window);
     commands.insert_resource(game_boundary);
-
-    // x, y of the cursor in the x,y grid, will be used in the loop below
-    // initially at the bottom left corner
-    let (mut x_curr, mut y_curr) = (game_boundary.x_min, game_boundary.y_min);
-
-    // find the x,y coordinate of the background block
-    while x_curr <= game_boundary.x_max {
-        // from left to right
-        while y_curr <= game_boundary.y_max {
-            // from bottom to top
-            commands.spawn((
-                SpriteBundle {
-                    transform: Transform {
-                        translation: Vec3::new(x_curr, y_curr, -1.),
-                        scale: Vec3::new(GRASS_SCALE, GRASS_SCALE, GRASS_SCALE),
-                        ..default()
-                    },
-                    texture: asset_server.load("sprites/background/slimeBlock.png"),
-                    ..default()
-                },
-                Grass {},
-            ));
-            y_curr += GRASS_HEIGHT_SCALED;
-        }
-        y_curr = game_boundary.y_min;
-        x_curr += GRASS_WIDTH_SCALED;
-    }
-}
-
-const LIFE_ICON_HEIGHT: f32 = 50.;
-const LIFE_ICON_SCALE: f32 = LIFE_ICON_HEIGHT / CHARACTER_HEIGHT;
-const LIFE_ICON_WIDTH: f32 = CHARACTER_WIDTH * LIFE_ICON_SCALE;
-
-pub fn spawn_lifes(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
-    game_metadata: Res<GameMetadata>,
-) {
-    let window = window_query.get_single().unwrap();
-    let game_boundary = get_game_boundary(window);
-
-    for i in 0..game_metadata.lifes {
-        // spawn life icon
-        let texture: Handle<Image> = asset_server.load("sprites/characters/alienYellow_walk1.png");
-        commands.spawn((
-            SpriteBundle {
-                transform: Transform {
-                    translation: Vec3::new(
-                        game_boundary.x_min + LIFE_ICON_WIDTH * 1.2 * (i as f32),
-                        game_boundary.y_max - LIFE_ICON_HEIGHT / 2.,
-                        OVERLAY_IDX,
-                    ),
-                    scale: Vec3::new(LIFE_ICON_SCALE, LIFE_ICON_SCALE, LIFE_ICON_SCALE),
-                    ..default()
-                },
-                texture,
-                ..default()
-            }, 
-            LifeIcon {
-                count: i,
-            }
-        ));
-    }
 }
 
-pub fn show_scores(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    game_metadata: Res<GameMetadata>,
-    asset_server: Res<AssetServer>
+// every player starts the match with a full set of lifes
+pub fn init_game_metadata(
+    mut game_metadata: ResMut<GameMetadata>,
+    mut level_timer: ResMut<LevelTimer>,
+    match_settings: Res<MatchSettings>,
+    run_rules: Res<RunRules>,
 ) {
-    let window = window_query.get_single().unwrap();
-    let game_boundary = get_game_boundary(window);
-
-    let font_size = 40.0;
-
-    let root = commands.spawn((
-        CurrentScoreRoot,
-        NodeBundle {
-            // give it a dark background for readability
-            background_color: BackgroundColor(Color::BLACK.with_a(0.5)),
-            // make it "always on top" by setting the Z index to maximum
-            // we want it to be displayed over all other UI
-            z_index: ZIndex::Global(i32::MAX),
-            style: Style {
-                position_type: PositionType::Absolute,
-                // position it at the top-right corner
-                // 1% away from the top window edge
-                right: Val::Auto,
-                top: Val::Auto,
-                align_content: AlignContent::End,
-                // set bottom/left to Auto, so it can be
-                // automatically sized depending on the text
-                bottom: Val::Px(game_boundary.y_max - font_size),
-                width: Val::Px(WINDOW_WIDTH / 2.),
-                left: Val::Px(game_boundary.x_min + WINDOW_WIDTH / 2.),
-                // give it some padding for readability
-                padding: UiRect::all(Val::Px(4.0)),
-                ..Default::default()
-            },
-            ..Default::default()
-        },
-    )).id();
-    // show score icon on the top right
-    let text_fps = commands.spawn((
-        CurrentScore,
-        TextBundle {
-            // use two sections, so it is easy to update just the number
-            text: Text::from_sections([
-                TextSection {
-                    value: game_metadata.scores.to_string(),
-                    style: TextStyle {
-                        font_size,
-                        color: Color::WHITE,
-                        font: asset_server.load("BungeeSpice-Regular.ttf"),
-                        ..default()
-                    }
-                },
-            ]).with_alignment(TextAlignment::Right),
-            ..Default::default()
-        },
-    )).id();
-    commands.entity(root).push_children(&[text_fps]);
-
+    *game_metadata = GameMetadata::with_rules(match_settings.players.clamp(1, PLAYER_TINTS.len()), &run_rules);
+    *level_timer = LevelTimer::new(run_rules.level_duration);
 }
 
 // subscribe to LoseLifeEvent
@@ -186,38 +63,68 @@ pub fn update_user_life(
     mut commands: Commands,
     mut lose_life_events: EventReader<LoseLifeEvent>,
     mut game_over_event: EventWriter<GameOver>,
-    life_icon_query: Query<(Entity, &LifeIcon)>,
+    character_query: Query<(Entity, &Character)>,
     mut game_metadata: ResMut<GameMetadata>,
+    match_settings: Res<MatchSettings>,
 ) {
-    /* @todo check game over */
-    for _ in lose_life_events.read() {
-        game_metadata.lifes -= 1;
-        
-        for (entity, life_icon) in life_icon_query.iter() {   
-            if life_icon.count >= game_metadata.lifes {
-                commands.entity(entity).despawn()
+    let is_over = |game_metadata: &GameMetadata| match match_settings.game_over {
+        GameOverRule::Shared => game_metadata.players.iter().any(|stats| stats.lifes == 0),
+        GameOverRule::Separate => game_metadata.players.iter().all(|stats| stats.lifes == 0),
+    };
+    // the game over is told once, on the tick the last life that mattered is lost
+    let was_over = is_over(&game_metadata);
+
+    for event in lose_life_events.read() {
+        let Some(stats) = game_metadata.players.get_mut(event.player) else {
+            continue;
+        };
+        stats.lifes = stats.lifes.saturating_sub(1);
+
+        if stats.lifes == 0 && match_settings.game_over == GameOverRule::Separate && match_settings.mode == MatchMode::CoOp {
+            for (entity, character) in character_query.iter() {
+                if character.player == event.player {
+                    commands.entity(entity).despawn();
+                }
             }
         }
     }
 
-    if game_metadata.lifes <= 0 {
-        game_over_event.send(GameOver {
-            score: 10,
-        })   
+    // versus rounds end on their own
+    if match_settings.mode == MatchMode::Versus {
+        return;
+    }
+
+    if !was_over && is_over(&game_metadata) {
+        game_over_event.send(GameOver);
     }
 }
 
 pub fn update_points(
     mut earn_point_events: EventReader<EarnPointEvent>,
     mut game_metadata: ResMut<GameMetadata>,
-    mut current_score_query: Query<&mut Text, With<CurrentScore>>
+    run_rules: Res<RunRules>,
 ) {
-    /* @todo check game over */
     for event in earn_point_events.read() {
-        game_metadata.scores += event.scores;
-        // update text display
-        for mut text in &mut current_score_query {
-            text.sections[0].value = game_metadata.scores.to_string();
+        let Some(stats) = game_metadata.players.get_mut(event.player) else {
+            continue;
         };
+        stats.scores += run_rules.scaled_score(event.scores);
+    }
+}
+
+
+// the game gets harder the longer the player survives
+pub fn level_up(
+    mut level_timer: ResMut<LevelTimer>,
+    mut game_metadata: ResMut<GameMetadata>,
+    mut level_up_event: EventWriter<LevelUpEvent>,
+    time: Res<Time>,
+) {
+    level_timer.timer.tick(time.delta());
+    if level_timer.timer.just_finished() {
+        game_metadata.level += 1.;
+        level_up_event.send(LevelUpEvent {
+            level: game_metadata.level,
+        });
     }
 }
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::objects::{components::{CollisionObject, HitboxSource}, hitbox::HitboxKind};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BossPhase {
+    Opening,
+    Enraged,
+    Frenzy,
+}
+
+impl BossPhase {
+    // the boss gets more aggressive as its encounter goes on
+    pub fn from_progress(progress: f32) -> Self {
+        if progress < 1. / 3. {
+            BossPhase::Opening
+        } else if progress < 2. / 3. {
+            BossPhase::Enraged
+        } else {
+            BossPhase::Frenzy
+        }
+    }
+
+    pub fn move_speed(&self) -> f32 {
+        match self {
+            BossPhase::Opening => 120.,
+            BossPhase::Enraged => 180.,
+            BossPhase::Frenzy => 260.,
+        }
+    }
+
+    pub fn fire_interval(&self) -> f32 {
+        match self {
+            BossPhase::Opening => 1.2,
+            BossPhase::Enraged => 1.0,
+            BossPhase::Frenzy => 0.8,
+        }
+    }
+
+    // horizontal offsets of each projectile of a volley, in character widths
+    pub fn pattern(&self) -> &'static [f32] {
+        match self {
+            BossPhase::Opening => &[0.],
+            BossPhase::Enraged => &[-1., 1.],
+            BossPhase::Frenzy => &[-2., 0., 2.],
+        }
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct Boss {
+    pub size: Vec3,
+    pub direction: f32,
+    pub spawned_at: f32,
+    pub phase: BossPhase,
+    pub fire_timer: Timer,
+}
+
+impl Boss {
+    pub fn new(width: f32, height: f32, spawned_at: f32) -> Self {
+        let phase = BossPhase::Opening;
+        Boss {
+            size: Vec3::new(width, height, 0.),
+            direction: 1.,
+            spawned_at,
+            phase,
+            fire_timer: Timer::from_seconds(phase.fire_interval(), TimerMode::Repeating),
+        }
+    }
+}
+
+impl CollisionObject for Boss {
+    fn get_dimension(&self) -> (f32, f32) {
+        (self.size.x, self.size.y)
+    }
+}
+
+impl HitboxSource for Boss {
+    fn hitbox_kind(&self) -> HitboxKind {
+        HitboxKind::Boss
+    }
+}
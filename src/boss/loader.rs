@@ -0,0 +1,22 @@
+use crate::data::RonAsset;
+
+use super::resources::BossSettings;
+
+impl RonAsset for BossSettings {
+    const NAME: &'static str = "boss settings";
+    const EXTENSIONS: &'static [&'static str] = &["boss.ron"];
+
+    fn validate(&mut self) -> Result<(), String> {
+        if self.milestones.is_empty() {
+            return Err("there must be at least one milestone".to_string());
+        }
+        if self.milestones.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err("milestones must be in ascending order".to_string());
+        }
+        // the phases follow the share of the encounter gone by
+        if self.duration <= 0. {
+            return Err("duration must be positive".to_string());
+        }
+        Ok(())
+    }
+}
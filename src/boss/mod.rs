@@ -0,0 +1,43 @@
+pub mod components;
+pub mod loader;
+pub mod resources;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, Condition, AssetEvent, not, resource_exists, on_event},
+    app::{Startup, Update, FixedUpdate},
+};
+
+use crate::{
+    background::systems::level_up, collision::CollisionSet, data::RonAssetApp, objects::systems::resolve_hitboxes,
+    simulation::{SimulationSet, resources::PendingAssets, rollback::RollbackApp, systems::release_simulation},
+    versus::systems::versus_match,
+};
+
+use self::{
+    components::Boss,
+    resources::BossSettings,
+    systems::{
+        load_boss_settings, apply_boss_settings, init_boss_texture, spawn_boss_on_milestone, boss_movement, boss_attack, boss_retreat, boss_contact
+    },
+};
+
+pub struct BossPlug;
+
+impl Plugin for BossPlug {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<BossSettings>()
+            .init_resource::<BossSettings>()
+            .rollback_component::<Boss>()
+            .add_systems(Startup, (load_boss_settings, init_boss_texture))
+            // kept up to date until the simulation starts, so the first milestones are the ones of the file
+            .add_systems(Update, apply_boss_settings
+                .run_if(resource_exists::<PendingAssets>().or_else(on_event::<AssetEvent<BossSettings>>()))
+                .before(release_simulation))
+            .add_systems(FixedUpdate, resolve_hitboxes::<Boss>.in_set(SimulationSet::Begin))
+            .add_systems(FixedUpdate, spawn_boss_on_milestone.after(level_up).run_if(not(versus_match)))
+            .add_systems(FixedUpdate, boss_movement.in_set(CollisionSet::Movement))
+            .add_systems(FixedUpdate, (boss_attack, boss_retreat).after(CollisionSet::Movement))
+            .add_systems(FixedUpdate, boss_contact.in_set(CollisionSet::Reaction));
+    }
+}
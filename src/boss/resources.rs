@@ -0,0 +1,37 @@
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+const BOSS_DEFAULT_MILESTONES: [u32; 3] = [3, 6, 9];
+const BOSS_DEFAULT_DURATION: f32 = 30.;
+const BOSS_DEFAULT_SCORE_BONUS: u32 = 200;
+
+#[derive(Resource)]
+pub struct BossTexture {
+    pub boss: Handle<Image>,
+    pub projectile: Handle<Image>,
+}
+
+// set in `assets/data/default.boss.ron`, these defaults stand in until it is loaded or when it cannot be
+#[derive(Resource, Asset, TypePath, Deserialize, Clone, PartialEq)]
+pub struct BossSettings {
+    // levels at which a boss shows up
+    pub milestones: Vec<u32>,
+    // seconds the player has to survive the boss
+    pub duration: f32,
+    pub score_bonus: u32,
+}
+
+#[derive(Resource)]
+pub struct BossSettingsHandle {
+    pub handle: Handle<BossSettings>,
+}
+
+impl Default for BossSettings {
+    fn default() -> Self {
+        BossSettings {
+            milestones: BOSS_DEFAULT_MILESTONES.to_vec(),
+            duration: BOSS_DEFAULT_DURATION,
+            score_bonus: BOSS_DEFAULT_SCORE_BONUS,
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    background::{
+        events::LevelUpEvent,
+        resources::{GameBonudary, GameMetadata, RunRules},
+    },
+    collision::events::CollisionEvent,
+    objects::{
+        components::{UfoType, Ufo},
+    },
+    player::{
+        components::Character,
+        events::{EarnPointEvent, LoseLifeEvent, PointSource},
+        systems::CHARACTER_WIDTH,
+    },
+    simulation::{resources::PendingAssets, rollback::Rollback},
+};
+
+use super::{
+    components::{Boss, BossPhase},
+    resources::{BossSettings, BossSettingsHandle, BossTexture},
+};
+
+use bevy::prelude::*;
+
+const BOSS_SCALE: f32 = 2.;
+const BOSS_WIDTH: f32 = 70. * BOSS_SCALE;
+const BOSS_HEIGHT: f32 = 47. * BOSS_SCALE;
+const BOSS_ENTRY_SPEED: f32 = 100.;
+const PROJECTILE_WIDTH: f32 = 45.;
+const PROJECTILE_HEIGHT: f32 = 60.;
+
+pub fn init_boss_texture(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pending_assets: ResMut<PendingAssets>,
+) {
+    let boss: Handle<Image> = asset_server.load("sprites/enemy/bat_70x47.png");
+    let projectile: Handle<Image> = asset_server.load("sprites/enemy/piranha_down.png");
+    pending_assets.handles.push(projectile.clone().untyped());
+
+    commands.insert_resource(BossTexture { boss, projectile });
+}
+
+pub fn load_boss_settings(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pending_assets: ResMut<PendingAssets>,
+) {
+    let handle: Handle<BossSettings> = asset_server.load("data/default.boss.ron");
+    pending_assets.handles.push(handle.clone().untyped());
+    commands.insert_resource(BossSettingsHandle { handle });
+}
+
+// a boss already around keeps its duration once the file changes
+pub fn apply_boss_settings(
+    mut boss_settings: ResMut<BossSettings>,
+    settings_assets: Res<Assets<BossSettings>>,
+    settings_handle: Res<BossSettingsHandle>,
+) {
+    let Some(settings) = settings_assets.get(&settings_handle.handle) else {
+        return;
+    };
+    if *boss_settings != *settings {
+        *boss_settings = settings.clone();
+    }
+}
+
+// regular spawning is put on hold while a boss is around
+pub fn no_boss(boss_query: Query<(), With<Boss>>) -> bool {
+    boss_query.is_empty()
+}
+
+pub fn spawn_boss_on_milestone(
+    mut commands: Commands,
+    mut level_up_events: EventReader<LevelUpEvent>,
+    boss_query: Query<(), With<Boss>>,
+    boss_settings: Res<BossSettings>,
+    texture: Res<BossTexture>,
+    game_boundary: Res<GameBonudary>,
+    time: Res<Time>,
+) {
+    for event in level_up_events.read() {
+        if !boss_settings.milestones.contains(&(event.level as u32)) || !boss_query.is_empty() {
+            continue;
+        }
+
+        // the boss comes in from above the play area
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform {
+                    translation: Vec3::new(game_boundary.x_mid, game_boundary.y_max + BOSS_HEIGHT, 1.),
+                    scale: Vec3::new(BOSS_SCALE, BOSS_SCALE, 1.),
+                    ..default()
+                },
+                texture: texture.boss.clone(),
+                ..default()
+            },
+            Boss::new(BOSS_WIDTH, BOSS_HEIGHT, time.elapsed_seconds()),
+            Rollback,
+        ));
+        return;
+    }
+}
+
+// come down to the top of the play area, then sweep from side to side
+pub fn boss_movement(
+    mut boss_query: Query<(&mut Transform, &mut Boss)>,
+    game_boundary: Res<GameBonudary>,
+    time: Res<Time>,
+) {
+    let hover_y = game_boundary.y_max - BOSS_HEIGHT / 2.;
+    let x_min = game_boundary.x_min + BOSS_WIDTH / 2.;
+    let x_max = game_boundary.x_max - BOSS_WIDTH / 2.;
+
+    for (mut transform, mut boss) in boss_query.iter_mut() {
+        if transform.translation.y > hover_y {
+            transform.translation.y = (transform.translation.y - BOSS_ENTRY_SPEED * time.delta_seconds()).max(hover_y);
+            continue;
+        }
+
+        transform.translation.x += boss.direction * boss.phase.move_speed() * time.delta_seconds();
+        if transform.translation.x > x_max {
+            transform.translation.x = x_max;
+            boss.direction = -1.;
+        } else if transform.translation.x < x_min {
+            transform.translation.x = x_min;
+            boss.direction = 1.;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn boss_attack(
+    mut commands: Commands,
+    mut boss_query: Query<(&Transform, &mut Boss)>,
+    boss_settings: Res<BossSettings>,
+    texture: Res<BossTexture>,
+    game_boundary: Res<GameBonudary>,
+    game_metadata: Res<GameMetadata>,
+    run_rules: Res<RunRules>,
+    time: Res<Time>,
+) {
+    for (transform, mut boss) in boss_query.iter_mut() {
+        // hold fire until the boss has fully entered the play area
+        if transform.translation.y > game_boundary.y_max - BOSS_HEIGHT / 2. {
+            continue;
+        }
+
+        let progress = (time.elapsed_seconds() - boss.spawned_at) / boss_settings.duration;
+        let phase = BossPhase::from_progress(progress);
+        if phase != boss.phase {
+            boss.phase = phase;
+            boss.fire_timer = Timer::from_seconds(phase.fire_interval(), TimerMode::Repeating);
+        }
+
+        boss.fire_timer.tick(time.delta());
+        if !boss.fire_timer.just_finished() {
+            continue;
+        }
+
+        for offset in boss.phase.pattern() {
+            let x = (transform.translation.x + offset * CHARACTER_WIDTH)
+                .clamp(game_boundary.x_min, game_boundary.x_max);
+            let translation = Vec3::new(x, transform.translation.y - BOSS_HEIGHT / 2., 0.);
+            let projectile = Ufo::steady(game_metadata.level, PROJECTILE_WIDTH, PROJECTILE_HEIGHT, 0, UfoType::Enemy);
+            spawn_boss_projectile(&mut commands, texture.projectile.clone(), translation, projectile, &run_rules);
+        }
+    }
+}
+
+// the boss stays on top whatever the gravity, so its projectiles leave from it as they are
+// and head down to the characters, against the gravity when it is mirrored
+fn spawn_boss_projectile(
+    commands: &mut Commands,
+    texture: Handle<Image>,
+    translation: Vec3,
+    mut projectile: Ufo,
+    run_rules: &RunRules,
+) {
+    projectile.size *= run_rules.ufo_scale;
+    projectile.fall_speed *= -run_rules.fall_direction;
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_translation(translation)
+                .with_scale(Vec3::new(run_rules.ufo_scale, run_rules.ufo_scale, 1.)),
+            texture,
+            ..default()
+        },
+        projectile,
+        Rollback,
+    ));
+}
+
+// surviving the whole encounter is worth a large bonus to every player still around
+pub fn boss_retreat(
+    mut commands: Commands,
+    mut earn_point_event: EventWriter<EarnPointEvent>,
+    boss_query: Query<(Entity, &Boss)>,
+    character_query: Query<&Character>,
+    boss_settings: Res<BossSettings>,
+    time: Res<Time>,
+) {
+    for (entity, boss) in boss_query.iter() {
+        if time.elapsed_seconds() - boss.spawned_at >= boss_settings.duration {
+            commands.entity(entity).despawn();
+            for character in character_query.iter() {
+                earn_point_event.send(EarnPointEvent {
+                    player: character.player,
+                    scores: boss_settings.score_bonus,
+                    source: PointSource::Boss,
+                });
+            }
+        }
+    }
+}
+
+// touching the boss hurts like any enemy, but the boss stays
+pub fn boss_contact(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut lose_life_event: EventWriter<LoseLifeEvent>,
+    mut character_query: Query<&mut Character>,
+    boss_query: Query<(), With<Boss>>,
+    run_rules: Res<RunRules>,
+    time: Res<Time>,
+) {
+    for event in collision_events.read() {
+        if !boss_query.contains(event.b) {
+            continue;
+        }
+        if let Ok(mut character) = character_query.get_mut(event.a) {
+            if character.hit(time.elapsed_seconds(), run_rules.invulnerable_duration) {
+                lose_life_event.send(LoseLifeEvent {
+                    player: character.player,
+                });
+            }
+        }
+    }
+}
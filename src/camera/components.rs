@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct CameraController {
+    // where the camera rests, the middle of the window
+    pub home: Vec3,
+    // from 0 to 1, the shake grows with its square and both wear off over time
+    pub trauma: f32,
+    // from 0 to 1, how far zoomed in
+    pub punch: f32,
+    // offset toward the characters
+    pub follow: Vec2,
+}
+
+impl CameraController {
+    pub fn new(home: Vec3) -> Self {
+        CameraController {
+            home,
+            trauma: 0.,
+            punch: 0.,
+            follow: Vec2::ZERO,
+        }
+    }
+
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.);
+    }
+}
@@ -0,0 +1,21 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, not}, app::Update};
+
+use crate::netplay::systems::netplay_active;
+
+use self::{resources::CameraSettings, systems::{camera_reactions, move_camera}};
+
+// screen shake, zoom punches and an optional follow of the characters, all turned off
+// together by the camera motion setting of the menu. online matches get no reactions,
+// a rollback would replay the events behind them
+pub struct CameraPlug;
+
+impl Plugin for CameraPlug {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CameraSettings::load())
+            .add_systems(Update, (camera_reactions.run_if(not(netplay_active)), move_camera).chain());
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::saves::{load_save, write_save};
+
+const CAMERA_SAVE: &str = "camera";
+
+// how the camera may move, kept from one game to the next
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraSettings {
+    // the camera leans a little toward the characters
+    pub follow: bool,
+    // turned off for anyone bothered by the shakes and zooms, the camera then stays still
+    pub motion: bool,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        CameraSettings {
+            follow: false,
+            motion: true,
+        }
+    }
+}
+
+impl CameraSettings {
+    pub fn load() -> Self {
+        load_save(CAMERA_SAVE)
+    }
+
+    pub fn save(&self) {
+        write_save(CAMERA_SAVE, self);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    background::events::LevelUpEvent,
+    player::{components::Character, events::{EarnPointEvent, LoseLifeEvent, PointSource}},
+    systems::GameOver,
+};
+
+use super::{components::CameraController, resources::CameraSettings};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+const LIFE_LOST_TRAUMA: f32 = 0.6;
+const TRAUMA_DECAY: f32 = 1.2;
+const MAX_SHAKE_OFFSET: f32 = 14.;
+const MAX_SHAKE_ANGLE: f32 = 0.04;
+// how much closer the camera gets at the top of a zoom punch
+const ZOOM_PUNCH: f32 = 0.08;
+const PUNCH_DECAY: f32 = 3.;
+// part of the way to the characters the camera leans, and how fast it gets there
+const FOLLOW_STRENGTH: f32 = 0.12;
+const FOLLOW_RATE: f32 = 4.;
+
+// losing a life shakes the camera, level ups, beaten bosses and game overs punch it in
+pub fn camera_reactions(
+    mut lose_life_events: EventReader<LoseLifeEvent>,
+    mut level_up_events: EventReader<LevelUpEvent>,
+    mut earn_point_events: EventReader<EarnPointEvent>,
+    mut game_over_events: EventReader<GameOver>,
+    mut camera_query: Query<&mut CameraController>,
+) {
+    let trauma = lose_life_events.read().count() as f32 * LIFE_LOST_TRAUMA;
+    let boss_beaten = earn_point_events.read().any(|event| event.source == PointSource::Boss);
+    let punch = level_up_events.read().count() > 0 || game_over_events.read().count() > 0 || boss_beaten;
+
+    for mut controller in camera_query.iter_mut() {
+        controller.add_trauma(trauma);
+        if punch {
+            controller.punch = 1.;
+        }
+    }
+}
+
+// the camera runs on real time, a paused game does not leave it off its rest
+pub fn move_camera(
+    mut camera_query: Query<(&mut CameraController, &mut Transform, &mut OrthographicProjection)>,
+    character_query: Query<&Transform, (With<Character>, Without<CameraController>)>,
+    camera_settings: Res<CameraSettings>,
+    time: Res<Time<Real>>,
+) {
+    let delta = time.delta_seconds();
+    let count = character_query.iter().count();
+    let target = match count {
+        0 => Vec2::ZERO,
+        _ => character_query.iter().map(|transform| transform.translation.truncate()).sum::<Vec2>() / count as f32,
+    };
+    let mut rng = rand::thread_rng();
+
+    for (mut controller, mut transform, mut projection) in camera_query.iter_mut() {
+        if !camera_settings.motion {
+            controller.trauma = 0.;
+            controller.punch = 0.;
+            controller.follow = Vec2::ZERO;
+        }
+
+        controller.trauma = (controller.trauma - TRAUMA_DECAY * delta).max(0.);
+        controller.punch = (controller.punch - PUNCH_DECAY * delta).max(0.);
+        let follow_target = if camera_settings.follow && camera_settings.motion && count > 0 {
+            (target - controller.home.truncate()) * FOLLOW_STRENGTH
+        } else {
+            Vec2::ZERO
+        };
+        let follow = controller.follow;
+        controller.follow = follow + (follow_target - follow) * (1. - (-FOLLOW_RATE * delta).exp());
+
+        let shake = controller.trauma * controller.trauma;
+        let offset = Vec2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0)) * MAX_SHAKE_OFFSET * shake;
+        transform.translation = controller.home + (controller.follow + offset).extend(0.);
+        transform.rotation = Quat::from_rotation_z(rng.gen_range(-1.0..=1.0) * MAX_SHAKE_ANGLE * shake);
+        // the punch eases out
+        projection.scale = 1. - ZOOM_PUNCH * controller.punch * controller.punch;
+    }
+}
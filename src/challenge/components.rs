@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+// today's challenge and its best scores, shown under the menu
+#[derive(Component)]
+pub struct DailyBoard;
@@ -0,0 +1,32 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, OnEnter, OnExit, resource_exists, not}, app::{Startup, Last}};
+
+use crate::{background::systems::init_game_metadata, replay::systems::replay_playing, systems::AppState};
+
+use self::{
+    resources::DailyChallenge,
+    systems::{
+        load_daily_leaderboard, spawn_daily_board, despawn_daily_board, apply_daily_challenge, enter_daily_attempt,
+        save_daily_score
+    },
+};
+
+// a run picked from the menu whose seed and modifiers come from the date,
+// so everyone playing on the same day gets the same one. one attempt a day is scored
+pub struct ChallengePlug;
+
+impl Plugin for ChallengePlug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_daily_leaderboard)
+            .add_systems(OnEnter(AppState::Menu), spawn_daily_board)
+            .add_systems(OnExit(AppState::Menu), despawn_daily_board)
+            .add_systems(OnEnter(AppState::Playing), (
+                apply_daily_challenge.before(init_game_metadata),
+                enter_daily_attempt.run_if(not(replay_playing)),
+            ).run_if(resource_exists::<DailyChallenge>()))
+            .add_systems(Last, save_daily_score.run_if(resource_exists::<DailyChallenge>()));
+    }
+}
@@ -0,0 +1,193 @@
+use std::{
+    cmp::Reverse,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{background::resources::RunRules, saves::{load_save, write_save}};
+
+const LEADERBOARD_SAVE: &str = "daily";
+const MODIFIERS_PER_DAY: usize = 2;
+const SECONDS_PER_DAY: u64 = 86_400;
+pub const BOARD_SIZE: usize = 5;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ChallengeModifier {
+    FastEnemies,
+    OneLife,
+    DoubleBonuses,
+    LateStart,
+}
+
+impl ChallengeModifier {
+    pub const ALL: [ChallengeModifier; 4] = [
+        ChallengeModifier::FastEnemies, ChallengeModifier::OneLife, ChallengeModifier::DoubleBonuses, ChallengeModifier::LateStart
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChallengeModifier::FastEnemies => "fast enemies",
+            ChallengeModifier::OneLife => "one life",
+            ChallengeModifier::DoubleBonuses => "double bonuses",
+            ChallengeModifier::LateStart => "late start",
+        }
+    }
+
+    pub fn apply(&self, rules: &mut RunRules) {
+        match self {
+            ChallengeModifier::FastEnemies => rules.enemy_speed *= 1.5,
+            ChallengeModifier::OneLife => rules.starting_lifes = 1,
+            ChallengeModifier::DoubleBonuses => rules.bonus_score *= 2,
+            ChallengeModifier::LateStart => rules.starting_level = 3.,
+        }
+    }
+}
+
+// the same run for everyone on the same day: the seed and the modifiers only depend on the date
+#[derive(Resource, Serialize, Deserialize, Clone)]
+pub struct DailyChallenge {
+    // utc date, as yyyy-mm-dd
+    pub date: String,
+    pub seed: u64,
+    pub modifiers: Vec<ChallengeModifier>,
+    // only the first attempt of the day goes on the leaderboard
+    #[serde(skip)]
+    pub scored: bool,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs() / SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days as i64);
+        DailyChallenge::for_date(year, month, day)
+    }
+
+    pub fn for_date(year: i64, month: u32, day: u32) -> Self {
+        let seed = split_mix((year as u64) * 10_000 + (month as u64) * 100 + day as u64);
+        let mut modifiers = ChallengeModifier::ALL.to_vec();
+        modifiers.shuffle(&mut StdRng::seed_from_u64(seed));
+        modifiers.truncate(MODIFIERS_PER_DAY);
+
+        DailyChallenge {
+            date: format!("{year:04}-{month:02}-{day:02}"),
+            seed,
+            modifiers,
+            scored: false,
+        }
+    }
+
+    // the modifiers go on top of the rules of the game mode
+    pub fn apply(&self, rules: &mut RunRules) {
+        for modifier in self.modifiers.iter() {
+            modifier.apply(rules);
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let modifiers: Vec<&str> = self.modifiers.iter().map(ChallengeModifier::label).collect();
+        format!("daily {}: {}", self.date, modifiers.join(", "))
+    }
+}
+
+// spreads neighbouring dates far apart, the same on every platform
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// (year, month, day) of the days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DailyEntry {
+    pub date: String,
+    pub player: String,
+    pub score: u32,
+}
+
+// every scored attempt at a daily challenge on this machine, kept apart from the regular runs
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct DailyLeaderboard {
+    pub entries: Vec<DailyEntry>,
+}
+
+impl DailyLeaderboard {
+    pub fn load() -> Self {
+        load_save(LEADERBOARD_SAVE)
+    }
+
+    pub fn save(&self) {
+        write_save(LEADERBOARD_SAVE, self);
+    }
+
+    pub fn entry_mut(&mut self, date: &str, player: &str) -> Option<&mut DailyEntry> {
+        self.entries.iter_mut().find(|entry| entry.date == date && entry.player == player)
+    }
+
+    // best scores of the day, highest first
+    pub fn best_of(&self, date: &str) -> Vec<&DailyEntry> {
+        let mut entries: Vec<&DailyEntry> = self.entries.iter().filter(|entry| entry.date == date).collect();
+        entries.sort_by_key(|entry| Reverse(entry.score));
+        entries.truncate(BOARD_SIZE);
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_since_the_epoch_give_the_date() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(59), (1970, 3, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn leap_days_are_counted() {
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(11_017), (2000, 3, 1));
+        assert_eq!(civil_from_days(47_541), (2100, 3, 1));
+    }
+
+    #[test]
+    fn the_same_date_gives_the_same_challenge() {
+        let (first, second) = (DailyChallenge::for_date(2024, 5, 17), DailyChallenge::for_date(2024, 5, 17));
+        assert_eq!(first.seed, second.seed);
+        assert!(first.modifiers == second.modifiers);
+        assert_eq!(first.date, "2024-05-17");
+    }
+
+    #[test]
+    fn neighbouring_dates_get_other_seeds() {
+        assert_ne!(DailyChallenge::for_date(2024, 5, 17).seed, DailyChallenge::for_date(2024, 5, 18).seed);
+        assert_ne!(DailyChallenge::for_date(2024, 5, 17).seed, DailyChallenge::for_date(2025, 5, 17).seed);
+    }
+
+    #[test]
+    fn a_challenge_has_distinct_modifiers() {
+        let challenge = DailyChallenge::for_date(2024, 5, 17);
+        assert_eq!(challenge.modifiers.len(), MODIFIERS_PER_DAY);
+        assert!(challenge.modifiers[0] != challenge.modifiers[1]);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    background::resources::{GameMetadata, RunRules},
+    saves::player_name,
+    simulation::resources::GameRng,
+};
+
+use super::{
+    components::DailyBoard,
+    resources::{DailyChallenge, DailyEntry, DailyLeaderboard},
+};
+
+use bevy::{app::AppExit, prelude::*};
+
+const BOARD_FONT_SIZE: f32 = 20.;
+
+pub fn load_daily_leaderboard(mut commands: Commands) {
+    commands.insert_resource(DailyLeaderboard::load());
+}
+
+pub fn spawn_daily_board(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    leaderboard: Res<DailyLeaderboard>,
+) {
+    let challenge = DailyChallenge::today();
+    let mut lines = vec![challenge.describe()];
+    let best = leaderboard.best_of(&challenge.date);
+    if best.is_empty() {
+        lines.push("no attempt yet today".to_string());
+    }
+    for (rank, entry) in best.iter().enumerate() {
+        lines.push(format!("{}. {} {}", rank + 1, entry.player, entry.score));
+    }
+
+    commands.spawn((
+        DailyBoard,
+        TextBundle {
+            z_index: ZIndex::Global(i32::MAX),
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(24.),
+                left: Val::Px(24.),
+                ..default()
+            },
+            text: Text::from_section(
+                lines.join("\n"),
+                TextStyle {
+                    font_size: BOARD_FONT_SIZE,
+                    color: Color::WHITE,
+                    font: asset_server.load("BungeeSpice-Regular.ttf"),
+                },
+            ),
+            ..default()
+        },
+    ));
+}
+
+pub fn despawn_daily_board(mut commands: Commands, board_query: Query<Entity, With<DailyBoard>>) {
+    for entity in board_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// the challenge replaces the seed and changes the rules of the run
+pub fn apply_daily_challenge(
+    challenge: Res<DailyChallenge>,
+    mut game_rng: ResMut<GameRng>,
+    mut run_rules: ResMut<RunRules>,
+) {
+    *game_rng = GameRng::from_seed(challenge.seed);
+    challenge.apply(&mut run_rules);
+    info!("{}", challenge.describe());
+}
+
+// the attempt counts as soon as it starts, quitting early does not give another one
+pub fn enter_daily_attempt(
+    mut challenge: ResMut<DailyChallenge>,
+    mut leaderboard: ResMut<DailyLeaderboard>,
+) {
+    let player = player_name();
+    if leaderboard.entry_mut(&challenge.date, &player).is_some() {
+        info!("today's challenge has already been played, this run is not scored");
+        return;
+    }
+
+    challenge.scored = true;
+    leaderboard.entries.push(DailyEntry {
+        date: challenge.date.clone(),
+        player,
+        score: 0,
+    });
+    leaderboard.save();
+}
+
+pub fn save_daily_score(
+    mut app_exit_event_reader: EventReader<AppExit>,
+    mut challenge: ResMut<DailyChallenge>,
+    mut leaderboard: ResMut<DailyLeaderboard>,
+    game_metadata: Res<GameMetadata>,
+) {
+    if app_exit_event_reader.read().last().is_none() || !challenge.scored {
+        return;
+    }
+
+    if let Some(entry) = leaderboard.entry_mut(&challenge.date, &player_name()) {
+        entry.score = game_metadata.scores();
+        info!("daily challenge {} scored {}", challenge.date, entry.score);
+    }
+    leaderboard.save();
+    // the exit event may be read again before the app actually closes
+    challenge.scored = false;
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+// objects that look for collisions, everything else with a hitbox is only collided with
+#[derive(Component, Clone, Copy)]
+pub struct CollisionProbe;
+
+// translation at the start of the tick, to know the path travelled since
+#[derive(Component, Clone, Copy)]
+pub struct PreviousTranslation(pub Vec3);
+
+// short tint of a probe after it ran into something
+#[derive(Component, Clone)]
+pub struct ImpactFlash {
+    pub timer: Timer,
+    pub original_color: Color,
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+// `a` is the probe (character, projectile) that ran into `b`
+#[derive(Event)]
+pub struct CollisionEvent {
+    pub a: Entity,
+    pub b: Entity,
+}
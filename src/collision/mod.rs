@@ -0,0 +1,48 @@
+pub mod components;
+pub mod events;
+pub mod resources;
+pub mod stress;
+pub mod systems;
+
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, IntoSystemSetConfigs, SystemSet}, app::{FixedUpdate, Update}};
+
+use crate::simulation::rollback::RollbackApp;
+
+use self::{
+    components::{CollisionProbe, PreviousTranslation, ImpactFlash},
+    events::CollisionEvent,
+    resources::SpatialGrid,
+    systems::{record_previous_translation, rebuild_spatial_grid, detect_collisions, impact_flash, fade_impact_flash},
+};
+
+// every tick, collision objects remember where they start and are moved,
+// then indexed and tested against each other along the path they travelled,
+// then every interested system reacts to the resulting collision events
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CollisionSet {
+    History,
+    Movement,
+    BroadPhase,
+    Detection,
+    Reaction,
+}
+
+pub struct CollisionPlug;
+
+impl Plugin for CollisionPlug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialGrid>()
+            .add_event::<CollisionEvent>()
+            .rollback_component::<CollisionProbe>()
+            .rollback_component::<PreviousTranslation>()
+            .rollback_component::<ImpactFlash>()
+            .configure_sets(FixedUpdate, (
+                CollisionSet::History, CollisionSet::Movement, CollisionSet::BroadPhase, CollisionSet::Detection, CollisionSet::Reaction
+            ).chain())
+            .add_systems(FixedUpdate, record_previous_translation.in_set(CollisionSet::History))
+            .add_systems(FixedUpdate, rebuild_spatial_grid.in_set(CollisionSet::BroadPhase))
+            .add_systems(FixedUpdate, detect_collisions.in_set(CollisionSet::Detection))
+            .add_systems(FixedUpdate, impact_flash.in_set(CollisionSet::Reaction))
+            .add_systems(Update, fade_impact_flash);
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::simulation::rollback::RollbackId;
+
+// cells are a bit bigger than an ufo so most of them sit in one to four cells
+const GRID_CELL_SIZE: f32 = 80.;
+
+// uniform grid of every ufo, rebuilt each frame, to only test the ufo close to an object.
+// entities are ordered by their rollback id, entity ids differ between peers and after a rollback
+#[derive(Resource)]
+pub struct SpatialGrid {
+    pub cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Option<RollbackId>, Entity)>>,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        SpatialGrid {
+            cell_size: GRID_CELL_SIZE,
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl SpatialGrid {
+    fn cell_range(&self, bounds: Rect) -> (IVec2, IVec2) {
+        (
+            (bounds.min / self.cell_size).floor().as_ivec2(),
+            (bounds.max / self.cell_size).floor().as_ivec2(),
+        )
+    }
+
+    // empty every cell but keep their allocation for the next frame
+    pub fn clear(&mut self) {
+        for entities in self.cells.values_mut() {
+            entities.clear();
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, id: Option<RollbackId>, bounds: Rect) {
+        let (min, max) = self.cell_range(bounds);
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                self.cells.entry(IVec2::new(x, y)).or_default().push((id, entity));
+            }
+        }
+    }
+
+    // every entity sharing a cell with the given bounds, each listed once in rollback id order
+    pub fn candidates(&self, bounds: Rect) -> Vec<Entity> {
+        let (min, max) = self.cell_range(bounds);
+        let mut candidates = Vec::new();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                if let Some(entities) = self.cells.get(&IVec2::new(x, y)) {
+                    candidates.extend_from_slice(entities);
+                }
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates.into_iter().map(|(_, entity)| entity).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_follow_the_rollback_ids() {
+        let mut spatial_grid = SpatialGrid::default();
+        let bounds = Rect::new(0., 0., 10., 10.);
+        let (first, second) = (Entity::from_raw(1), Entity::from_raw(2));
+        spatial_grid.insert(first, Some(RollbackId(8)), bounds);
+        spatial_grid.insert(second, Some(RollbackId(3)), Rect::new(0., 0., 200., 10.));
+        assert_eq!(spatial_grid.candidates(bounds), vec![second, first]);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    background::{resources::{GameBonudary, RunRules}, systems::{WINDOW_WIDTH, WINDOW_HEIGHT}},
+    objects::{
+        components::{UfoType, Ufo},
+        resources::EnemyTexture,
+        systems::{pick_texture, spawn_ufo_at, UFO_HEIGHT, UFO_WIDTH},
+    },
+    systems::cli_arg,
+};
+
+use bevy::{
+    app::AppExit,
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+use rand::{random, thread_rng};
+
+const STRESS_REPORT_INTERVAL: f32 = 2.;
+
+// benchmark scenario: keeps thousands of ufo on screen and reports the frame time,
+// which should stay flat as the count grows thanks to the spatial grid.
+// no wave or enemy comes down meanwhile, and the mean and 99th percentile frame times are printed on exit.
+// run with `cargo run --release -- --stress 5000`
+pub struct StressTestPlug {
+    pub count: usize,
+}
+
+#[derive(Resource)]
+pub struct StressTest {
+    pub count: usize,
+    pub report_timer: Timer,
+    // milliseconds of every frame since the population was first complete
+    pub frame_times: Vec<f32>,
+}
+
+impl Plugin for StressTestPlug {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(FrameTimeDiagnosticsPlugin)
+            .insert_resource(StressTest {
+                count: self.count,
+                report_timer: Timer::from_seconds(STRESS_REPORT_INTERVAL, TimerMode::Repeating),
+                frame_times: Vec::new(),
+            })
+            .add_systems(Update, (keep_stress_population, report_stress_frame_time))
+            .add_systems(Last, summarize_stress_frame_times);
+    }
+}
+
+// reads the ufo count from `--stress <count>`
+pub fn stress_test_count() -> Option<usize> {
+    cli_arg("--stress")
+}
+
+pub fn stress_test_running(stress_test: Option<Res<StressTest>>) -> bool {
+    stress_test.is_some()
+}
+
+// stress ufo are worthless bonuses, so the run never ends while measuring
+fn keep_stress_population(
+    mut commands: Commands,
+    stress_test: Res<StressTest>,
+    texture: Option<Res<EnemyTexture>>,
+    game_boundary: Option<Res<GameBonudary>>,
+    run_rules: Res<RunRules>,
+    ufo_query: Query<(), With<Ufo>>,
+) {
+    let (Some(texture), Some(game_boundary)) = (texture, game_boundary) else {
+        return;
+    };
+
+    for _ in ufo_query.iter().count()..stress_test.count {
+        let translation = Vec3::new(
+            game_boundary.x_min + random::<f32>() * WINDOW_WIDTH,
+            game_boundary.y_min + random::<f32>() * WINDOW_HEIGHT,
+            0.,
+        );
+        let ufo = Ufo::new(1., UFO_WIDTH, UFO_HEIGHT, 0, UfoType::Bonus, &mut thread_rng());
+        spawn_ufo_at(&mut commands, pick_texture(&texture.handles, &mut thread_rng()), translation, ufo, &run_rules, &game_boundary);
+    }
+}
+
+fn report_stress_frame_time(
+    mut stress_test: ResMut<StressTest>,
+    diagnostics: Res<DiagnosticsStore>,
+    ufo_query: Query<(), With<Ufo>>,
+    time: Res<Time>,
+    real_time: Res<Time<Real>>,
+) {
+    let count = ufo_query.iter().count();
+    if count >= stress_test.count || !stress_test.frame_times.is_empty() {
+        stress_test.frame_times.push(real_time.delta_seconds() * 1000.);
+    }
+
+    stress_test.report_timer.tick(time.delta());
+    if !stress_test.report_timer.just_finished() {
+        return;
+    }
+
+    if let Some(frame_time) = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|frame_time| frame_time.smoothed())
+    {
+        info!("stress test: {} ufo, {:.2} ms per frame", count, frame_time);
+    }
+}
+
+fn summarize_stress_frame_times(mut app_exit_event_reader: EventReader<AppExit>, stress_test: Res<StressTest>) {
+    if app_exit_event_reader.read().last().is_none() {
+        return;
+    }
+    match frame_time_summary(&stress_test.frame_times) {
+        Some((mean, p99)) => info!(
+            "stress test: {} ufo over {} frames, mean {:.2} ms, p99 {:.2} ms",
+            stress_test.count,
+            stress_test.frame_times.len(),
+            mean,
+            p99,
+        ),
+        None => info!("stress test: stopped before {} ufo were on screen", stress_test.count),
+    }
+}
+
+// mean and 99th percentile, nearest rank
+fn frame_time_summary(frame_times: &[f32]) -> Option<(f32, f32)> {
+    if frame_times.is_empty() {
+        return None;
+    }
+    let mut sorted = frame_times.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let mean = sorted.iter().sum::<f32>() / sorted.len() as f32;
+    let rank = (sorted.len() * 99).div_ceil(100);
+    Some((mean, sorted[rank - 1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_of_no_frame() {
+        assert_eq!(frame_time_summary(&[]), None);
+    }
+
+    #[test]
+    fn p99_is_the_slowest_percent() {
+        let mut frame_times = vec![10.; 99];
+        frame_times.push(50.);
+        assert_eq!(frame_time_summary(&frame_times), Some((10.4, 10.)));
+
+        frame_times.push(60.);
+        assert_eq!(frame_time_summary(&frame_times), Some(((990. + 110.) / 101., 50.)));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    objects::{components::CollisionObject, hitbox::Hitbox},
+    shooter::components::Projectile,
+    simulation::rollback::RollbackId,
+};
+
+use super::{
+    components::{CollisionProbe, ImpactFlash, PreviousTranslation},
+    events::CollisionEvent,
+    resources::SpatialGrid,
+};
+
+use bevy::prelude::*;
+
+const IMPACT_FLASH_DURATION: f32 = 0.15;
+const IMPACT_FLASH_COLOR: Color = Color::rgb(1., 0.5, 0.5);
+
+// remember where every collision object starts the tick
+pub fn record_previous_translation(
+    mut commands: Commands,
+    mut object_query: Query<(Entity, &Transform, Option<&mut PreviousTranslation>), With<Hitbox>>,
+) {
+    for (entity, transform, previous) in object_query.iter_mut() {
+        match previous {
+            Some(mut previous) => previous.0 = transform.translation,
+            None => {
+                commands.entity(entity).insert(PreviousTranslation(transform.translation));
+            },
+        }
+    }
+}
+
+fn previous_or_current(transform: &Transform, previous: Option<&PreviousTranslation>) -> Vec3 {
+    previous.map_or(transform.translation, |previous| previous.0)
+}
+
+// objects are indexed along the whole path they travelled this tick
+#[allow(clippy::type_complexity)]
+pub fn rebuild_spatial_grid(
+    mut spatial_grid: ResMut<SpatialGrid>,
+    object_query: Query<(Entity, &Transform, &Hitbox, Option<&PreviousTranslation>, Option<&RollbackId>), Without<CollisionProbe>>,
+) {
+    spatial_grid.clear();
+    for (entity, transform, hitbox, previous, id) in object_query.iter() {
+        let from = previous_or_current(transform, previous);
+        spatial_grid.insert(entity, id.copied(), hitbox.swept_bounds(from, transform.translation));
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn detect_collisions(
+    mut collision_event: EventWriter<CollisionEvent>,
+    probe_query: Query<(Entity, &Transform, &Hitbox, Option<&PreviousTranslation>, Option<&RollbackId>), With<CollisionProbe>>,
+    object_query: Query<(Entity, &Transform, &Hitbox, Option<&PreviousTranslation>), Without<CollisionProbe>>,
+    spatial_grid: Res<SpatialGrid>,
+) {
+    // the events come out in the same order on every peer
+    let mut probes: Vec<_> = probe_query.iter().collect();
+    probes.sort_by_key(|(entity, .., id)| (id.copied(), *entity));
+    for (probe_entity, probe_transform, probe_hitbox, probe_previous, _) in probes {
+        let probe_from = previous_or_current(probe_transform, probe_previous);
+        let candidates = spatial_grid.candidates(probe_hitbox.swept_bounds(probe_from, probe_transform.translation));
+        for (entity, transform, hitbox, previous) in object_query.iter_many(candidates) {
+            // fast objects may have stepped over each other since the last tick
+            let is_collide = probe_hitbox.collide(probe_transform.translation, hitbox, transform.translation)
+                || probe_hitbox.swept_intersects(
+                    probe_from,
+                    probe_transform.translation,
+                    hitbox,
+                    previous_or_current(transform, previous),
+                    transform.translation,
+                );
+            if is_collide {
+                collision_event.send(CollisionEvent {
+                    a: probe_entity,
+                    b: entity,
+                });
+            }
+        }
+    }
+}
+
+// projectiles are gone once they hit, only what survives the impact flashes
+pub fn impact_flash(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    sprite_query: Query<&Sprite, (Without<ImpactFlash>, Without<Projectile>)>,
+) {
+    for event in collision_events.read() {
+        if let Ok(sprite) = sprite_query.get(event.a) {
+            commands.entity(event.a).try_insert(ImpactFlash {
+                timer: Timer::from_seconds(IMPACT_FLASH_DURATION, TimerMode::Once),
+                original_color: sprite.color,
+            });
+        }
+    }
+}
+
+pub fn fade_impact_flash(
+    mut commands: Commands,
+    mut flash_query: Query<(Entity, &mut Sprite, &mut ImpactFlash)>,
+    time: Res<Time>,
+) {
+    for (entity, mut sprite, mut flash) in flash_query.iter_mut() {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            sprite.color = flash.original_color;
+            commands.entity(entity).remove::<ImpactFlash>();
+        } else {
+            sprite.color = IMPACT_FLASH_COLOR;
+        }
+    }
+}
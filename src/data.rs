@@ -0,0 +1,81 @@
+use std::{fmt, marker::PhantomData};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, LoadContext},
+    prelude::*,
+};
+use serde::de::DeserializeOwned;
+
+// gameplay data kept as ron files in `assets/data`, loaded and hot reloaded like any other asset
+pub trait RonAsset: Asset + DeserializeOwned {
+    // what the file holds, as shown in errors
+    const NAME: &'static str;
+    const EXTENSIONS: &'static [&'static str];
+
+    // rejects or cleans up what was parsed
+    fn validate(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+pub struct RonLoader<A>(PhantomData<fn() -> A>);
+
+impl<A> Default for RonLoader<A> {
+    fn default() -> Self {
+        RonLoader(PhantomData)
+    }
+}
+
+#[derive(Debug)]
+pub enum RonLoaderError {
+    Io(&'static str, std::io::Error),
+    Ron(&'static str, ron::error::SpannedError),
+    Invalid(&'static str, String),
+}
+
+impl fmt::Display for RonLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RonLoaderError::Io(name, err) => write!(f, "could not read {name}: {err}"),
+            RonLoaderError::Ron(name, err) => write!(f, "could not parse {name}: {err}"),
+            RonLoaderError::Invalid(name, reason) => write!(f, "invalid {name}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for RonLoaderError {}
+
+impl<A: RonAsset> AssetLoader for RonLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = RonLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<A, RonLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await.map_err(|err| RonLoaderError::Io(A::NAME, err))?;
+            let mut asset: A = ron::de::from_bytes(&bytes).map_err(|err| RonLoaderError::Ron(A::NAME, err))?;
+            asset.validate().map_err(|reason| RonLoaderError::Invalid(A::NAME, reason))?;
+            Ok(asset)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        A::EXTENSIONS
+    }
+}
+
+pub trait RonAssetApp {
+    fn init_ron_asset<A: RonAsset>(&mut self) -> &mut Self;
+}
+
+impl RonAssetApp for App {
+    fn init_ron_asset<A: RonAsset>(&mut self) -> &mut Self {
+        self.init_asset::<A>().init_asset_loader::<RonLoader<A>>()
+    }
+}
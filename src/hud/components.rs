@@ -0,0 +1,50 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct HudRoot;
+
+// the columns along the edges of the playfield, other plugins add their own labels to them
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum HudSlot {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+// shown as long as the player has more than `count` lifes
+#[derive(Component)]
+pub struct LifeIcon {
+    pub count: u8,
+    pub player: usize,
+}
+
+#[derive(Component)]
+pub struct ScoreLabel {
+    pub player: usize,
+}
+
+#[derive(Component)]
+pub struct LevelLabel;
+
+// only shown when the points are scaled, e.g. by the mutators
+#[derive(Component)]
+pub struct MultiplierLabel;
+
+#[derive(Component)]
+pub enum TimerLabel {
+    NextLevel,
+    // time left of a time attack run
+    TimeLeft,
+}
+
+// dash cooldown and invulnerability of a character
+#[derive(Component)]
+pub struct PowerUpLabel {
+    pub player: usize,
+}
+
+// only shown in shooter mode
+#[derive(Component)]
+pub struct AmmoLabel;
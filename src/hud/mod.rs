@@ -0,0 +1,38 @@
+pub mod components;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, OnEnter, OnExit, SystemSet, apply_deferred, in_state},
+    app::Update,
+};
+
+use crate::{background::systems::init_game_metadata, systems::AppState};
+
+use self::systems::{
+    spawn_hud, despawn_hud, update_life_icons, update_score_labels, update_level_label, update_multiplier_label,
+    update_timer_labels, update_power_up_labels, update_ammo_label
+};
+
+// the hud is spawned with the match, its slots can be filled by the systems running after this set
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HudSet {
+    Spawn,
+}
+
+// lifes, scores, level, score multiplier, timers and power-ups, laid out with flex boxes
+// along the edges of the playfield. the labels only read the game state, nothing is rolled back
+pub struct HudPlug;
+
+impl Plugin for HudPlug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Playing), (spawn_hud, apply_deferred)
+                .chain()
+                .after(init_game_metadata)
+                .in_set(HudSet::Spawn))
+            .add_systems(OnExit(AppState::Playing), despawn_hud)
+            .add_systems(Update, (
+                update_life_icons, update_score_labels, update_level_label, update_multiplier_label, update_timer_labels,
+                update_power_up_labels, update_ammo_label
+            ).run_if(in_state(AppState::Playing)));
+    }
+}
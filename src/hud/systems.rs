@@ -0,0 +1,274 @@
+use crate::{
+    background::{
+        resources::{GameMetadata, LevelTimer, RunRules},
+        systems::{WINDOW_HEIGHT, WINDOW_WIDTH},
+    },
+    player::{
+        components::Character,
+        resources::CharacterTexture,
+        systems::{CHARACTER_HEIGHT, CHARACTER_WIDTH, PLAYER_TINTS},
+    },
+    shooter::resources::{Ammo, ShooterSettings},
+};
+
+use super::components::{
+    AmmoLabel, HudRoot, HudSlot, LevelLabel, LifeIcon, MultiplierLabel, PowerUpLabel, ScoreLabel, TimerLabel,
+};
+
+use bevy::prelude::*;
+
+const SCORE_FONT_SIZE: f32 = 40.;
+const TIMER_FONT_SIZE: f32 = 32.;
+const LABEL_FONT_SIZE: f32 = 20.;
+const HUD_SPACING: f32 = 4.;
+const LIFE_ICON_HEIGHT: f32 = 50.;
+const LIFE_ICON_WIDTH: f32 = CHARACTER_WIDTH * LIFE_ICON_HEIGHT / CHARACTER_HEIGHT;
+
+fn label(value: impl Into<String>, font_size: f32, color: Color, font: &Handle<Font>) -> TextBundle {
+    TextBundle::from_section(value, TextStyle { font_size, color, font: font.clone() })
+}
+
+// the three slots of a bar share its width evenly, so the middle one stays centred
+fn slot_node(align_items: AlignItems) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.,
+            flex_basis: Val::Px(0.),
+            align_items,
+            row_gap: Val::Px(HUD_SPACING),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+fn bar_node(align_items: AlignItems) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            align_items,
+            ..default()
+        },
+        ..default()
+    }
+}
+
+// lifes, score and power-ups of a player, the lifes go from the corner of the playfield inwards
+fn spawn_player_panel(
+    slot: &mut ChildBuilder,
+    player: usize,
+    game_metadata: &GameMetadata,
+    character_texture: &CharacterTexture,
+    font: &Handle<Font>,
+) {
+    let Some(stats) = game_metadata.players.get(player) else {
+        return;
+    };
+    let tint = PLAYER_TINTS[player];
+
+    slot.spawn(NodeBundle {
+        style: Style {
+            flex_direction: if player == 0 { FlexDirection::Row } else { FlexDirection::RowReverse },
+            column_gap: Val::Px(LIFE_ICON_WIDTH * 0.2),
+            ..default()
+        },
+        ..default()
+    }).with_children(|row| {
+        for count in 0..stats.lifes {
+            row.spawn((
+                LifeIcon { count, player },
+                ImageBundle {
+                    style: Style {
+                        width: Val::Px(LIFE_ICON_WIDTH),
+                        height: Val::Px(LIFE_ICON_HEIGHT),
+                        ..default()
+                    },
+                    image: UiImage::new(character_texture.tail.clone()),
+                    background_color: BackgroundColor(tint),
+                    ..default()
+                },
+            ));
+        }
+    });
+    slot.spawn((ScoreLabel { player }, label(stats.scores.to_string(), SCORE_FONT_SIZE, tint, font)));
+    slot.spawn((PowerUpLabel { player }, label("", LABEL_FONT_SIZE, tint, font)));
+}
+
+// a box the size of the playfield, centred in the window like it, with a bar along its top and bottom edges
+pub fn spawn_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    character_texture: Res<CharacterTexture>,
+    game_metadata: Res<GameMetadata>,
+) {
+    let font = asset_server.load("BungeeSpice-Regular.ttf");
+
+    commands.spawn((
+        HudRoot,
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+    )).with_children(|root| {
+        root.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(WINDOW_WIDTH),
+                height: Val::Px(WINDOW_HEIGHT),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::SpaceBetween,
+                padding: UiRect::all(Val::Px(HUD_SPACING)),
+                ..default()
+            },
+            ..default()
+        }).with_children(|playfield| {
+            playfield.spawn(bar_node(AlignItems::FlexStart)).with_children(|bar| {
+                bar.spawn((HudSlot::TopLeft, slot_node(AlignItems::FlexStart))).with_children(|slot| {
+                    spawn_player_panel(slot, 0, &game_metadata, &character_texture, &font);
+                });
+                bar.spawn((HudSlot::TopCenter, slot_node(AlignItems::Center))).with_children(|slot| {
+                    slot.spawn((TimerLabel::TimeLeft, label("", TIMER_FONT_SIZE, Color::WHITE, &font)));
+                    slot.spawn((LevelLabel, label("", LABEL_FONT_SIZE, Color::WHITE, &font)));
+                    slot.spawn((TimerLabel::NextLevel, label("", LABEL_FONT_SIZE, Color::WHITE, &font)));
+                    slot.spawn((MultiplierLabel, label("", LABEL_FONT_SIZE, Color::WHITE, &font)));
+                });
+                bar.spawn((HudSlot::TopRight, slot_node(AlignItems::FlexEnd))).with_children(|slot| {
+                    spawn_player_panel(slot, 1, &game_metadata, &character_texture, &font);
+                });
+            });
+            playfield.spawn(bar_node(AlignItems::FlexEnd)).with_children(|bar| {
+                bar.spawn((HudSlot::BottomLeft, slot_node(AlignItems::FlexStart))).with_children(|slot| {
+                    slot.spawn((AmmoLabel, label("", LABEL_FONT_SIZE, Color::WHITE, &font)));
+                });
+                bar.spawn((HudSlot::BottomRight, slot_node(AlignItems::FlexEnd)));
+            });
+        });
+    });
+}
+
+pub fn despawn_hud(mut commands: Commands, hud_query: Query<Entity, With<HudRoot>>) {
+    for entity in hud_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn set_label(text: &mut Text, label: String) {
+    if text.sections[0].value != label {
+        text.sections[0].value = label;
+    }
+}
+
+// the lifes follow the game metadata, so they come back along with it on a rollback
+pub fn update_life_icons(
+    game_metadata: Res<GameMetadata>,
+    mut life_icon_query: Query<(&LifeIcon, &mut Style)>,
+) {
+    for (life_icon, mut style) in life_icon_query.iter_mut() {
+        let lifes = game_metadata.players.get(life_icon.player).map_or(0, |stats| stats.lifes);
+        let display = if life_icon.count < lifes { Display::Flex } else { Display::None };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+pub fn update_score_labels(
+    game_metadata: Res<GameMetadata>,
+    mut score_query: Query<(&mut Text, &ScoreLabel)>,
+) {
+    for (mut text, score_label) in score_query.iter_mut() {
+        let scores = game_metadata.players.get(score_label.player).map_or(0, |stats| stats.scores);
+        set_label(&mut text, scores.to_string());
+    }
+}
+
+pub fn update_level_label(
+    game_metadata: Res<GameMetadata>,
+    mut level_query: Query<&mut Text, With<LevelLabel>>,
+) {
+    for mut text in level_query.iter_mut() {
+        set_label(&mut text, format!("level {}", game_metadata.level as u32));
+    }
+}
+
+pub fn update_multiplier_label(
+    run_rules: Res<RunRules>,
+    mut multiplier_query: Query<&mut Text, With<MultiplierLabel>>,
+) {
+    let label = if run_rules.score_multiplier != 1. {
+        format!("score x{:.2}", run_rules.score_multiplier)
+    } else {
+        String::new()
+    };
+    for mut text in multiplier_query.iter_mut() {
+        set_label(&mut text, label.clone());
+    }
+}
+
+// the timers follow the run time of the simulation
+pub fn update_timer_labels(
+    level_timer: Res<LevelTimer>,
+    run_rules: Res<RunRules>,
+    time: Res<Time<Fixed>>,
+    mut timer_query: Query<(&mut Text, &TimerLabel)>,
+) {
+    for (mut text, timer_label) in timer_query.iter_mut() {
+        let label = match timer_label {
+            TimerLabel::NextLevel => format!("next level {}s", level_timer.timer.remaining_secs().ceil() as u32),
+            TimerLabel::TimeLeft => run_rules.time_limit.map_or_else(String::new, |time_limit| {
+                let left = (time_limit - time.elapsed_seconds()).max(0.).ceil() as u32;
+                format!("{}:{:02}", left / 60, left % 60)
+            }),
+        };
+        set_label(&mut text, label);
+    }
+}
+
+fn power_up_label(character: &Character, now: f32) -> String {
+    let cooldown = character.dash_cooldown(now);
+    let mut label = if cooldown > 0. {
+        format!("dash {:.1}", cooldown)
+    } else {
+        "dash ready".to_string()
+    };
+    if character.invulnerable_until > now {
+        label.push_str(&format!(" shield {:.1}", character.invulnerable_until - now));
+    }
+    label
+}
+
+pub fn update_power_up_labels(
+    character_query: Query<&Character>,
+    mut power_up_query: Query<(&mut Text, &PowerUpLabel)>,
+    time: Res<Time<Fixed>>,
+) {
+    for (mut text, power_up) in power_up_query.iter_mut() {
+        // a player out of the playfield has nothing to show
+        let label = character_query.iter()
+            .find(|character| character.player == power_up.player)
+            .map_or_else(String::new, |character| power_up_label(character, time.elapsed_seconds()));
+        set_label(&mut text, label);
+    }
+}
+
+pub fn update_ammo_label(
+    ammo: Res<Ammo>,
+    shooter_settings: Res<ShooterSettings>,
+    mut ammo_query: Query<&mut Text, With<AmmoLabel>>,
+) {
+    let label = if shooter_settings.enabled {
+        format!("ammo {}/{}", ammo.count, shooter_settings.max_ammo)
+    } else {
+        String::new()
+    };
+    for mut text in ammo_query.iter_mut() {
+        set_label(&mut text, label.clone());
+    }
+}
@@ -1,24 +1,93 @@
+mod achievements;
 mod background;
+mod boss;
+mod camera;
+mod challenge;
+mod collision;
+mod data;
+mod hud;
+mod menu;
+mod modes;
+mod mutators;
+mod netplay;
 mod objects;
+mod particles;
 mod player;
+mod replay;
+mod saves;
+mod shooter;
+mod simulation;
+mod skins;
 mod systems;
+mod themes;
+mod versus;
+mod waves;
 
+use achievements::AchievementPlug;
 use background::BackgroundPlug;
+use boss::BossPlug;
+use camera::CameraPlug;
+use challenge::ChallengePlug;
+use collision::{CollisionPlug, stress::{stress_test_count, StressTestPlug}};
+use hud::HudPlug;
+use menu::{MenuPlug, systems::skip_menu};
+use modes::ModePlug;
+use mutators::MutatorPlug;
+use netplay::{NetplayPlug, systems::netplay_active};
 use objects::EnemyPlug;
+use particles::ParticlePlug;
 use player::CharacterPlug;
-use systems::{spawn_camera, exit_game, handle_game_over, GameOver};
+use replay::{ReplayPlug, ghost::{ghost_enabled, GhostPlug}, systems::replay_playing};
+use shooter::ShooterPlug;
+use simulation::SimulationPlug;
+use skins::SkinPlug;
+use systems::{spawn_camera, exit_game, handle_game_over, GameOver, AppState};
+use themes::ThemePlug;
+use versus::VersusPlug;
+use waves::WavePlug;
 
 use bevy::prelude::*;
 
 fn main() {
     /* @todo handle resize */
-    App::new()
-        .add_plugins(DefaultPlugins)
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins)
+        .add_state::<AppState>()
         .add_systems(Startup, spawn_camera)
         .add_event::<GameOver>()
+        .add_plugins(SimulationPlug)
+        .add_plugins(ReplayPlug)
+        .add_plugins(MenuPlug)
+        .add_plugins(NetplayPlug)
+        .add_plugins(ChallengePlug)
+        .add_plugins(ModePlug)
+        .add_plugins(MutatorPlug)
+        .add_plugins(AchievementPlug)
+        .add_plugins(SkinPlug)
         .add_plugins(BackgroundPlug)
+        .add_plugins(ThemePlug)
+        .add_plugins(HudPlug)
+        .add_plugins(CollisionPlug)
         .add_plugins(CharacterPlug)
         .add_plugins(EnemyPlug)
-        .add_systems(Update, (exit_game, handle_game_over))
-        .run()
+        .add_plugins(WavePlug)
+        .add_plugins(BossPlug)
+        .add_plugins(ShooterPlug)
+        .add_plugins(VersusPlug)
+        .add_plugins(ParticlePlug)
+        .add_plugins(CameraPlug)
+        .add_systems(Update, exit_game)
+        // a replay ends on its own once its last tick has been played, an online match once its end is confirmed
+        .add_systems(Update, handle_game_over.run_if(not(replay_playing)).run_if(not(netplay_active)));
+
+    if ghost_enabled() {
+        app.add_plugins(GhostPlug);
+    }
+
+    if let Some(count) = stress_test_count() {
+        app.add_plugins(StressTestPlug { count })
+            .add_systems(Startup, skip_menu);
+    }
+
+    app.run()
 }
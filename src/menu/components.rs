@@ -0,0 +1,33 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct MenuRoot;
+
+// a line of the menu, listed from top to bottom
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum MenuEntry {
+    Mode,
+    GameMode,
+    // opens the mutator screen
+    Mutators,
+    // opens the skin screen
+    Skin,
+    Players,
+    GameOver,
+    // the camera leans toward the characters
+    CameraFollow,
+    // shakes, zoom punches and follow at all
+    CameraMotion,
+    Online,
+    // typed in while the entry is selected
+    Address,
+    Start,
+    Daily,
+}
+
+impl MenuEntry {
+    pub const ALL: [MenuEntry; 12] = [
+        MenuEntry::Mode, MenuEntry::GameMode, MenuEntry::Mutators, MenuEntry::Skin, MenuEntry::Players, MenuEntry::GameOver,
+        MenuEntry::CameraFollow, MenuEntry::CameraMotion, MenuEntry::Online, MenuEntry::Address, MenuEntry::Start, MenuEntry::Daily,
+    ];
+}
@@ -0,0 +1,23 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, OnEnter, OnExit, in_state}, app::Update};
+
+use crate::systems::AppState;
+
+use self::{
+    resources::MenuSelection,
+    systems::{spawn_menu, despawn_menu, navigate_menu, update_menu_entries},
+};
+
+pub struct MenuPlug;
+
+impl Plugin for MenuPlug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuSelection>()
+            .add_systems(OnEnter(AppState::Menu), spawn_menu)
+            .add_systems(OnExit(AppState::Menu), despawn_menu)
+            .add_systems(Update, (navigate_menu, update_menu_entries).chain().run_if(in_state(AppState::Menu)));
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::Resource;
+
+// index of the highlighted entry in `MenuEntry::ALL`
+#[derive(Resource, Default)]
+pub struct MenuSelection {
+    pub index: usize,
+}
@@ -0,0 +1,260 @@
+use crate::{
+    camera::resources::CameraSettings,
+    challenge::resources::DailyChallenge,
+    netplay::resources::{NetRole, NetSettings},
+    player::resources::{GameOverRule, MatchMode, MatchSettings},
+    simulation::resources::MAX_PLAYERS,
+    skins::resources::{Skin, SkinChoice, Skins},
+    systems::AppState,
+};
+
+use super::{
+    components::{MenuEntry, MenuRoot},
+    resources::MenuSelection,
+};
+
+use bevy::prelude::*;
+
+const TITLE_FONT_SIZE: f32 = 48.;
+const ENTRY_FONT_SIZE: f32 = 28.;
+const SELECTED_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+
+fn on_off(value: bool) -> &'static str {
+    if value { "on" } else { "off" }
+}
+
+fn entry_label(
+    entry: MenuEntry,
+    match_settings: &MatchSettings,
+    net_settings: &NetSettings,
+    skin: &Skin,
+    camera_settings: &CameraSettings,
+) -> String {
+    match entry {
+        MenuEntry::Mode => match match_settings.mode {
+            MatchMode::CoOp => "mode: co-op".to_string(),
+            MatchMode::Versus => "mode: versus".to_string(),
+        },
+        MenuEntry::GameMode => format!("game: {}", match_settings.game_mode.label()),
+        MenuEntry::Mutators => match match_settings.mutators.active().count() {
+            0 => "mutators: none".to_string(),
+            count => format!("mutators: {count}, score x{:.2}", match_settings.mutators.score_multiplier()),
+        },
+        MenuEntry::Skin => format!("skin: {}", skin.name),
+        MenuEntry::Players => format!("players: {}", match_settings.players),
+        MenuEntry::GameOver => match match_settings.game_over {
+            GameOverRule::Shared => "game over: shared".to_string(),
+            GameOverRule::Separate => "game over: separate".to_string(),
+        },
+        MenuEntry::CameraFollow => format!("camera follow: {}", on_off(camera_settings.follow)),
+        MenuEntry::CameraMotion => format!("camera motion: {}", on_off(camera_settings.motion)),
+        MenuEntry::Online => match net_settings.role {
+            NetRole::Offline => "online: off".to_string(),
+            NetRole::Host => "online: host".to_string(),
+            NetRole::Join => "online: join".to_string(),
+        },
+        MenuEntry::Address => format!("address: {}", net_settings.address),
+        MenuEntry::Start => "start".to_string(),
+        MenuEntry::Daily => "daily challenge".to_string(),
+    }
+}
+
+pub fn spawn_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    match_settings: Res<MatchSettings>,
+    net_settings: Res<NetSettings>,
+    skins: Res<Skins>,
+    skin_choice: Res<SkinChoice>,
+    camera_settings: Res<CameraSettings>,
+) {
+    let font = asset_server.load("BungeeSpice-Regular.ttf");
+
+    commands.spawn((
+        MenuRoot,
+        NodeBundle {
+            background_color: BackgroundColor(Color::BLACK.with_a(0.7)),
+            z_index: ZIndex::Global(i32::MAX),
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(12.),
+                ..default()
+            },
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "alien dodge ball",
+            TextStyle {
+                font_size: TITLE_FONT_SIZE,
+                color: Color::WHITE,
+                font: font.clone(),
+            },
+        ));
+        for entry in MenuEntry::ALL {
+            parent.spawn((
+                entry,
+                TextBundle::from_section(
+                    entry_label(entry, &match_settings, &net_settings, skins.worn(&skin_choice), &camera_settings),
+                    TextStyle {
+                        font_size: ENTRY_FONT_SIZE,
+                        color: Color::WHITE,
+                        font: font.clone(),
+                    },
+                ),
+            ));
+        }
+    });
+}
+
+pub fn despawn_menu(mut commands: Commands, menu_query: Query<Entity, With<MenuRoot>>) {
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// up and down pick an entry, left, right and enter change it, enter on start begins the match.
+// an online match goes through the lobby first
+#[allow(clippy::too_many_arguments)]
+pub fn navigate_menu(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut received_characters: EventReader<ReceivedCharacter>,
+    mut menu_selection: ResMut<MenuSelection>,
+    mut match_settings: ResMut<MatchSettings>,
+    mut net_settings: ResMut<NetSettings>,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let entries = MenuEntry::ALL.len();
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        menu_selection.index = (menu_selection.index + entries - 1) % entries;
+    }
+    if keyboard_input.just_pressed(KeyCode::Down) {
+        menu_selection.index = (menu_selection.index + 1) % entries;
+    }
+
+    if MenuEntry::ALL[menu_selection.index] == MenuEntry::Address {
+        for event in received_characters.read() {
+            if event.char.is_ascii_alphanumeric() || matches!(event.char, '.' | ':' | '-') {
+                net_settings.address.push(event.char);
+            }
+        }
+        if keyboard_input.just_pressed(KeyCode::Back) {
+            net_settings.address.pop();
+        }
+    } else {
+        received_characters.clear();
+    }
+
+    if !keyboard_input.any_just_pressed([KeyCode::Left, KeyCode::Right, KeyCode::Return]) {
+        return;
+    }
+    match MenuEntry::ALL[menu_selection.index] {
+        // versus takes exactly two players
+        MenuEntry::Mode => {
+            match_settings.mode = match match_settings.mode {
+                MatchMode::CoOp => MatchMode::Versus,
+                MatchMode::Versus => MatchMode::CoOp,
+            };
+            if match_settings.mode == MatchMode::Versus {
+                match_settings.players = 2;
+            }
+        },
+        MenuEntry::GameMode => {
+            match_settings.game_mode = match_settings.game_mode.next();
+        },
+        MenuEntry::Mutators => {
+            if keyboard_input.just_pressed(KeyCode::Return) {
+                next_state.set(AppState::Mutators);
+            }
+        },
+        MenuEntry::Skin => {
+            if keyboard_input.just_pressed(KeyCode::Return) {
+                next_state.set(AppState::Skins);
+            }
+        },
+        MenuEntry::Players => {
+            match_settings.players = match_settings.players % MAX_PLAYERS + 1;
+            // an online match is always played by two
+            if net_settings.role != NetRole::Offline {
+                match_settings.players = 2;
+            }
+            if match_settings.players < 2 {
+                match_settings.mode = MatchMode::CoOp;
+            }
+        },
+        MenuEntry::GameOver => {
+            match_settings.game_over = match match_settings.game_over {
+                GameOverRule::Shared => GameOverRule::Separate,
+                GameOverRule::Separate => GameOverRule::Shared,
+            };
+        },
+        MenuEntry::CameraFollow => {
+            camera_settings.follow = !camera_settings.follow;
+            camera_settings.save();
+        },
+        MenuEntry::CameraMotion => {
+            camera_settings.motion = !camera_settings.motion;
+            camera_settings.save();
+        },
+        MenuEntry::Online => {
+            net_settings.role = match net_settings.role {
+                NetRole::Offline => NetRole::Host,
+                NetRole::Host => NetRole::Join,
+                NetRole::Join => NetRole::Offline,
+            };
+            if net_settings.role != NetRole::Offline {
+                match_settings.players = 2;
+            }
+        },
+        MenuEntry::Address => {},
+        MenuEntry::Start => {
+            if keyboard_input.just_pressed(KeyCode::Return) {
+                match net_settings.role {
+                    NetRole::Offline => next_state.set(AppState::Playing),
+                    _ => next_state.set(AppState::Lobby),
+                }
+            }
+        },
+        // the daily challenge is the same solo run for everyone
+        MenuEntry::Daily => {
+            if keyboard_input.just_pressed(KeyCode::Return) {
+                *match_settings = MatchSettings::default();
+                net_settings.role = NetRole::Offline;
+                commands.insert_resource(DailyChallenge::today());
+                next_state.set(AppState::Playing);
+            }
+        },
+    }
+}
+
+pub fn update_menu_entries(
+    menu_selection: Res<MenuSelection>,
+    match_settings: Res<MatchSettings>,
+    net_settings: Res<NetSettings>,
+    skins: Res<Skins>,
+    skin_choice: Res<SkinChoice>,
+    camera_settings: Res<CameraSettings>,
+    mut entry_query: Query<(&mut Text, &MenuEntry)>,
+) {
+    if !menu_selection.is_changed() && !match_settings.is_changed() && !net_settings.is_changed() && !camera_settings.is_changed() && !skins.is_changed() {
+        return;
+    }
+
+    for (mut text, entry) in entry_query.iter_mut() {
+        let selected = MenuEntry::ALL[menu_selection.index] == *entry;
+        text.sections[0].value = entry_label(*entry, &match_settings, &net_settings, skins.worn(&skin_choice), &camera_settings);
+        text.sections[0].style.color = if selected { SELECTED_COLOR } else { Color::WHITE };
+    }
+}
+
+// runs that are not played by hand, e.g. replays, start right away
+pub fn skip_menu(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::Playing);
+}
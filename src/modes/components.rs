@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+// best scores of the game mode picked in the menu
+#[derive(Component)]
+pub struct HighScoreBoard;
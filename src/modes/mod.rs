@@ -0,0 +1,42 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, OnEnter, OnExit, in_state, not, resource_exists},
+    app::{Startup, FixedUpdate, Update, Last},
+};
+
+use crate::{
+    background::systems::{init_game_metadata, update_user_life},
+    challenge::{resources::DailyChallenge, systems::apply_daily_challenge},
+    replay::systems::replay_playing,
+    simulation::SimulationSet,
+    systems::AppState,
+    versus::systems::versus_match,
+};
+
+use self::systems::{
+    load_high_scores, apply_game_mode, end_on_time_limit, spawn_high_score_board,
+    update_high_score_board, despawn_high_score_board, save_high_score
+};
+
+// the rule set picked in the menu: endless, time attack or hardcore.
+// each of them keeps its own high scores, the daily challenge and versus matches are left out
+pub struct ModePlug;
+
+impl Plugin for ModePlug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_high_scores)
+            .add_systems(OnEnter(AppState::Menu), spawn_high_score_board)
+            .add_systems(OnExit(AppState::Menu), despawn_high_score_board)
+            .add_systems(Update, update_high_score_board.run_if(in_state(AppState::Menu)))
+            .add_systems(OnEnter(AppState::Playing), apply_game_mode.before(apply_daily_challenge).before(init_game_metadata))
+            .add_systems(FixedUpdate, end_on_time_limit.after(update_user_life).before(SimulationSet::End).run_if(not(versus_match)))
+            .add_systems(Last, save_high_score
+                .run_if(not(replay_playing))
+                .run_if(not(resource_exists::<DailyChallenge>()))
+                .run_if(not(versus_match))
+                .run_if(in_state(AppState::Playing)));
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    background::resources::{RunRules, LEVEL_DURATION},
+    mutators::resources::Mutators,
+    saves::{load_save, write_save},
+};
+
+const TIME_ATTACK_DURATION: f32 = 120.;
+const HARDCORE_LEVEL_DURATION: f32 = LEVEL_DURATION / 2.;
+const HIGH_SCORES_SAVE: &str = "high_scores";
+pub const HIGH_SCORES_KEPT: usize = 10;
+
+// the rule set of a run
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    // survive as long as possible
+    #[default]
+    Endless,
+    // score as much as possible before the countdown runs out
+    TimeAttack,
+    // a single life, no second chance after a hit, and levels go by twice as fast
+    Hardcore,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 3] = [GameMode::Endless, GameMode::TimeAttack, GameMode::Hardcore];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Endless => "endless",
+            GameMode::TimeAttack => "time attack",
+            GameMode::Hardcore => "hardcore",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = GameMode::ALL.iter().position(|mode| mode == self).unwrap_or_default();
+        GameMode::ALL[(index + 1) % GameMode::ALL.len()]
+    }
+
+    pub fn rules(&self) -> RunRules {
+        match self {
+            GameMode::Endless => RunRules::default(),
+            GameMode::TimeAttack => RunRules {
+                time_limit: Some(TIME_ATTACK_DURATION),
+                ..default()
+            },
+            GameMode::Hardcore => RunRules {
+                starting_lifes: 1,
+                invulnerable_duration: 0.,
+                level_duration: HARDCORE_LEVEL_DURATION,
+                ..default()
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HighScore {
+    pub player: String,
+    pub score: u32,
+}
+
+// the best runs of every game mode on this machine, a run with mutators only competes with the same mutators
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct HighScores {
+    pub lists: BTreeMap<(GameMode, Mutators), Vec<HighScore>>,
+}
+
+impl HighScores {
+    pub fn load() -> Self {
+        load_save(HIGH_SCORES_SAVE)
+    }
+
+    pub fn save(&self) {
+        write_save(HIGH_SCORES_SAVE, self);
+    }
+
+    pub fn list(&self, mode: GameMode, mutators: Mutators) -> &[HighScore] {
+        self.lists.get(&(mode, mutators)).map_or(&[], Vec::as_slice)
+    }
+
+    // best score of any game mode and mutators
+    pub fn best(&self) -> u32 {
+        self.lists.values().flatten().map(|high_score| high_score.score).max().unwrap_or_default()
+    }
+
+    // returns the rank of the score if it made it on the list
+    pub fn insert(&mut self, mode: GameMode, mutators: Mutators, high_score: HighScore) -> Option<usize> {
+        let list = self.lists.entry((mode, mutators)).or_default();
+        let rank = list.iter().position(|entry| entry.score < high_score.score).unwrap_or(list.len());
+        if rank >= HIGH_SCORES_KEPT {
+            return None;
+        }
+        list.insert(rank, high_score);
+        list.truncate(HIGH_SCORES_KEPT);
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mutators::resources::Mutator;
+
+    use super::*;
+
+    fn high_score(score: u32) -> HighScore {
+        HighScore { player: "player".to_string(), score }
+    }
+
+    fn scores(high_scores: &HighScores, mode: GameMode) -> Vec<u32> {
+        high_scores.list(mode, Mutators::default()).iter().map(|high_score| high_score.score).collect()
+    }
+
+    #[test]
+    fn scores_are_ranked_highest_first() {
+        let mut high_scores = HighScores::default();
+        assert_eq!(high_scores.insert(GameMode::Endless, Mutators::default(), high_score(10)), Some(0));
+        assert_eq!(high_scores.insert(GameMode::Endless, Mutators::default(), high_score(30)), Some(0));
+        assert_eq!(high_scores.insert(GameMode::Endless, Mutators::default(), high_score(20)), Some(1));
+        assert_eq!(scores(&high_scores, GameMode::Endless), vec![30, 20, 10]);
+    }
+
+    #[test]
+    fn a_tie_goes_after_the_older_score() {
+        let mut high_scores = HighScores::default();
+        high_scores.insert(GameMode::Endless, Mutators::default(), high_score(10));
+        assert_eq!(high_scores.insert(GameMode::Endless, Mutators::default(), high_score(10)), Some(1));
+    }
+
+    #[test]
+    fn only_the_best_scores_are_kept() {
+        let mut high_scores = HighScores::default();
+        for score in 1..=HIGH_SCORES_KEPT as u32 {
+            high_scores.insert(GameMode::Endless, Mutators::default(), high_score(score * 10));
+        }
+        assert_eq!(high_scores.insert(GameMode::Endless, Mutators::default(), high_score(5)), None);
+        assert_eq!(high_scores.insert(GameMode::Endless, Mutators::default(), high_score(15)), Some(HIGH_SCORES_KEPT - 1));
+
+        let kept = scores(&high_scores, GameMode::Endless);
+        assert_eq!(kept.len(), HIGH_SCORES_KEPT);
+        assert_eq!(kept.last(), Some(&15));
+    }
+
+    #[test]
+    fn every_mode_has_its_own_list() {
+        let mut high_scores = HighScores::default();
+        high_scores.insert(GameMode::Endless, Mutators::default(), high_score(10));
+        high_scores.insert(GameMode::Hardcore, Mutators::default(), high_score(40));
+        assert_eq!(scores(&high_scores, GameMode::Endless), vec![10]);
+        assert!(high_scores.list(GameMode::TimeAttack, Mutators::default()).is_empty());
+        assert_eq!(high_scores.best(), 40);
+    }
+
+    #[test]
+    fn runs_with_mutators_have_their_own_list() {
+        let mut giant = Mutators::default();
+        giant.toggle(Mutator::GiantUfos);
+        let mut high_scores = HighScores::default();
+        high_scores.insert(GameMode::Endless, Mutators::default(), high_score(10));
+        assert_eq!(high_scores.insert(GameMode::Endless, giant, high_score(5)), Some(0));
+
+        assert_eq!(scores(&high_scores, GameMode::Endless), vec![10]);
+        assert_eq!(high_scores.list(GameMode::Endless, giant).len(), 1);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    background::resources::{GameMetadata, RunRules},
+    player::resources::MatchSettings,
+    saves::player_name,
+    systems::GameOver,
+};
+
+use super::{
+    components::HighScoreBoard,
+    resources::{HighScore, HighScores},
+};
+
+use bevy::{app::AppExit, prelude::*};
+
+const BOARD_FONT_SIZE: f32 = 20.;
+
+pub fn load_high_scores(mut commands: Commands) {
+    commands.insert_resource(HighScores::load());
+}
+
+pub fn apply_game_mode(
+    mut run_rules: ResMut<RunRules>,
+    match_settings: Res<MatchSettings>,
+) {
+    *run_rules = match_settings.game_mode.rules();
+}
+
+// time attack is over once the countdown runs out, whatever the lifes left
+pub fn end_on_time_limit(
+    mut game_over_event: EventWriter<GameOver>,
+    run_rules: Res<RunRules>,
+    time: Res<Time>,
+) {
+    if run_rules.time_limit.is_some_and(|time_limit| time.elapsed_seconds() >= time_limit) {
+        game_over_event.send(GameOver);
+    }
+}
+
+fn board_label(high_scores: &HighScores, match_settings: &MatchSettings) -> String {
+    let mode = match_settings.game_mode;
+    let mut lines = vec![format!("{} high scores", mode.label())];
+    let mutators: Vec<&str> = match_settings.mutators.active().map(|mutator| mutator.label()).collect();
+    if !mutators.is_empty() {
+        lines.push(mutators.join(", "));
+    }
+    let list = high_scores.list(mode, match_settings.mutators);
+    if list.is_empty() {
+        lines.push("no run yet".to_string());
+    }
+    for (rank, high_score) in list.iter().enumerate() {
+        lines.push(format!("{}. {} {}", rank + 1, high_score.player, high_score.score));
+    }
+    lines.join("\n")
+}
+
+pub fn spawn_high_score_board(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    high_scores: Res<HighScores>,
+    match_settings: Res<MatchSettings>,
+) {
+    commands.spawn((
+        HighScoreBoard,
+        TextBundle {
+            z_index: ZIndex::Global(i32::MAX),
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(24.),
+                right: Val::Px(24.),
+                ..default()
+            },
+            text: Text::from_section(
+                board_label(&high_scores, &match_settings),
+                TextStyle {
+                    font_size: BOARD_FONT_SIZE,
+                    color: Color::WHITE,
+                    font: asset_server.load("BungeeSpice-Regular.ttf"),
+                },
+            ).with_alignment(TextAlignment::Right),
+            ..default()
+        },
+    ));
+}
+
+pub fn update_high_score_board(
+    mut board_query: Query<&mut Text, With<HighScoreBoard>>,
+    high_scores: Res<HighScores>,
+    match_settings: Res<MatchSettings>,
+) {
+    if !match_settings.is_changed() {
+        return;
+    }
+    for mut text in board_query.iter_mut() {
+        text.sections[0].value = board_label(&high_scores, &match_settings);
+    }
+}
+
+pub fn despawn_high_score_board(mut commands: Commands, board_query: Query<Entity, With<HighScoreBoard>>) {
+    for entity in board_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// the score of the team goes on the list of the game mode once the game exits
+pub fn save_high_score(
+    mut app_exit_event_reader: EventReader<AppExit>,
+    mut high_scores: ResMut<HighScores>,
+    mut saved: Local<bool>,
+    game_metadata: Res<GameMetadata>,
+    match_settings: Res<MatchSettings>,
+) {
+    if app_exit_event_reader.read().last().is_none() || *saved {
+        return;
+    }
+    // the exit event may be read again before the app actually closes
+    *saved = true;
+
+    let high_score = HighScore {
+        player: player_name(),
+        score: game_metadata.scores(),
+    };
+    if let Some(rank) = high_scores.insert(match_settings.game_mode, match_settings.mutators, high_score) {
+        info!("new {} high score, ranked {}", match_settings.game_mode.label(), rank + 1);
+        high_scores.save();
+    }
+}
@@ -0,0 +1,13 @@
+use bevy::prelude::Component;
+
+use super::resources::Mutator;
+
+#[derive(Component)]
+pub struct MutatorRoot;
+
+// a line of the mutator screen, `None` is the way back to the menu
+#[derive(Component)]
+pub struct MutatorEntry(pub Option<Mutator>);
+
+#[derive(Component)]
+pub struct MultiplierLabel;
@@ -0,0 +1,46 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, OnEnter, OnExit, in_state},
+    app::Update,
+};
+
+use crate::{
+    background::systems::init_game_metadata,
+    challenge::systems::apply_daily_challenge,
+    modes::systems::apply_game_mode,
+    player::systems::spawn_characters,
+    systems::AppState,
+};
+
+use self::{
+    resources::MutatorSelection,
+    systems::{
+        spawn_mutator_screen, despawn_mutator_screen, navigate_mutators, update_mutator_entries, apply_mutators, set_game_speed,
+        enemies_hidden, flash_enemies
+    },
+};
+
+// twists on the rules toggled from their own screen before a run, e.g. mirrored gravity or giant ufos.
+// every mutator changes the run rules, the score multiplier grows with how much harder they make the run
+pub struct MutatorPlug;
+
+impl Plugin for MutatorPlug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MutatorSelection>()
+            .add_systems(OnEnter(AppState::Mutators), spawn_mutator_screen)
+            .add_systems(OnExit(AppState::Mutators), despawn_mutator_screen)
+            .add_systems(Update, (navigate_mutators, update_mutator_entries).chain().run_if(in_state(AppState::Mutators)))
+            .add_systems(OnEnter(AppState::Playing), (
+                apply_mutators
+                    .after(apply_game_mode)
+                    .before(apply_daily_challenge)
+                    .before(init_game_metadata)
+                    .before(spawn_characters),
+                set_game_speed.after(apply_mutators),
+            ))
+            .add_systems(Update, flash_enemies.run_if(in_state(AppState::Playing)).run_if(enemies_hidden));
+    }
+}
@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::background::resources::RunRules;
+
+const GIANT_UFO_SCALE: f32 = 1.6;
+const TINY_HITBOX_SCALE: f32 = 0.5;
+const DOUBLE_SPEED: f32 = 2.;
+
+// a twist on the rules picked before a run, any of them can be combined
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mutator {
+    // ufos rise from the bottom
+    MirroredGravity,
+    TinyHitbox,
+    GiantUfos,
+    // enemies are only seen when they flash
+    InvisibleEnemies,
+    NoBonuses,
+    DoubleSpeed,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 6] = [
+        Mutator::MirroredGravity, Mutator::TinyHitbox, Mutator::GiantUfos, Mutator::InvisibleEnemies, Mutator::NoBonuses,
+        Mutator::DoubleSpeed,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Mutator::MirroredGravity => "mirrored gravity",
+            Mutator::TinyHitbox => "tiny hitbox",
+            Mutator::GiantUfos => "giant ufos",
+            Mutator::InvisibleEnemies => "invisible enemies",
+            Mutator::NoBonuses => "no bonuses",
+            Mutator::DoubleSpeed => "double speed",
+        }
+    }
+
+    // how much the mutator changes the score, below 1 when it makes the run easier
+    pub fn score_multiplier(&self) -> f32 {
+        match self {
+            Mutator::MirroredGravity => 1.2,
+            Mutator::TinyHitbox => 0.6,
+            Mutator::GiantUfos => 1.4,
+            Mutator::InvisibleEnemies => 1.8,
+            // the bonuses are where most points come from
+            Mutator::NoBonuses => 2.,
+            Mutator::DoubleSpeed => 1.5,
+        }
+    }
+
+    fn bit(&self) -> u8 {
+        1 << Mutator::ALL.iter().position(|mutator| mutator == self).unwrap_or_default()
+    }
+
+    pub fn apply(&self, rules: &mut RunRules) {
+        match self {
+            Mutator::MirroredGravity => rules.fall_direction = -rules.fall_direction,
+            Mutator::TinyHitbox => rules.character_hitbox_scale *= TINY_HITBOX_SCALE,
+            Mutator::GiantUfos => rules.ufo_scale *= GIANT_UFO_SCALE,
+            Mutator::InvisibleEnemies => rules.enemies_hidden = true,
+            Mutator::NoBonuses => rules.bonuses = false,
+            Mutator::DoubleSpeed => rules.game_speed *= DOUBLE_SPEED,
+        }
+        rules.score_multiplier *= self.score_multiplier();
+    }
+}
+
+// the mutators toggled on, one bit per mutator so the set stays small in replays and packets
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub struct Mutators {
+    bits: u8,
+}
+
+impl Mutators {
+    pub fn contains(&self, mutator: Mutator) -> bool {
+        self.bits & mutator.bit() != 0
+    }
+
+    pub fn toggle(&mut self, mutator: Mutator) {
+        self.bits ^= mutator.bit();
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = Mutator> + '_ {
+        Mutator::ALL.into_iter().filter(|mutator| self.contains(*mutator))
+    }
+
+    pub fn score_multiplier(&self) -> f32 {
+        self.active().map(|mutator| mutator.score_multiplier()).product()
+    }
+
+    pub fn apply(&self, rules: &mut RunRules) {
+        for mutator in self.active() {
+            mutator.apply(rules);
+        }
+    }
+}
+
+// index of the highlighted line, the line after the mutators goes back to the menu
+#[derive(Resource, Default)]
+pub struct MutatorSelection {
+    pub index: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_mutator_has_its_own_bit() {
+        let bits: Vec<u8> = Mutator::ALL.iter().map(Mutator::bit).collect();
+        for (index, bit) in bits.iter().enumerate() {
+            assert_eq!(bit.count_ones(), 1);
+            assert!(!bits[..index].contains(bit));
+        }
+    }
+
+    #[test]
+    fn toggling_twice_turns_a_mutator_off() {
+        let mut mutators = Mutators::default();
+        mutators.toggle(Mutator::GiantUfos);
+        mutators.toggle(Mutator::NoBonuses);
+        assert!(mutators.contains(Mutator::GiantUfos));
+        assert!(mutators.contains(Mutator::NoBonuses));
+        assert!(!mutators.contains(Mutator::TinyHitbox));
+
+        mutators.toggle(Mutator::GiantUfos);
+        assert!(!mutators.contains(Mutator::GiantUfos));
+        assert!(mutators.active().eq([Mutator::NoBonuses]));
+    }
+
+    #[test]
+    fn no_mutator_keeps_the_score() {
+        assert_eq!(Mutators::default().score_multiplier(), 1.);
+    }
+
+    #[test]
+    fn multipliers_of_combined_mutators_multiply() {
+        let mut mutators = Mutators::default();
+        mutators.toggle(Mutator::TinyHitbox);
+        mutators.toggle(Mutator::NoBonuses);
+        let expected = Mutator::TinyHitbox.score_multiplier() * Mutator::NoBonuses.score_multiplier();
+        assert!((mutators.score_multiplier() - expected).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn applied_mutators_carry_their_multiplier_into_the_rules() {
+        let mut mutators = Mutators::default();
+        mutators.toggle(Mutator::MirroredGravity);
+        mutators.toggle(Mutator::DoubleSpeed);
+        let (mut rules, defaults) = (RunRules::default(), RunRules::default());
+        mutators.apply(&mut rules);
+        assert_eq!(rules.fall_direction, -defaults.fall_direction);
+        assert_eq!(rules.game_speed, defaults.game_speed * DOUBLE_SPEED);
+        assert!((rules.score_multiplier - defaults.score_multiplier * mutators.score_multiplier()).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    background::resources::RunRules,
+    objects::components::{UfoType, Ufo},
+    player::resources::MatchSettings,
+    replay::resources::ReplayPlayback,
+    systems::AppState,
+};
+
+use super::{
+    components::{MultiplierLabel, MutatorEntry, MutatorRoot},
+    resources::{Mutator, MutatorSelection},
+};
+
+use bevy::prelude::*;
+
+const TITLE_FONT_SIZE: f32 = 40.;
+const ENTRY_FONT_SIZE: f32 = 28.;
+const SELECTED_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+// invisible enemies show up for a moment every period
+const FLASH_PERIOD: f32 = 1.5;
+const FLASH_DURATION: f32 = 0.2;
+
+fn entry_label(entry: Option<Mutator>, match_settings: &MatchSettings) -> String {
+    match entry {
+        Some(mutator) => {
+            let check = if match_settings.mutators.contains(mutator) { "x" } else { " " };
+            format!("[{check}] {}", mutator.label())
+        },
+        None => "back".to_string(),
+    }
+}
+
+fn multiplier_label(match_settings: &MatchSettings) -> String {
+    format!("score x{:.2}", match_settings.mutators.score_multiplier())
+}
+
+pub fn spawn_mutator_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    match_settings: Res<MatchSettings>,
+    mut mutator_selection: ResMut<MutatorSelection>,
+) {
+    let font = asset_server.load("BungeeSpice-Regular.ttf");
+    mutator_selection.index = 0;
+
+    commands.spawn((
+        MutatorRoot,
+        NodeBundle {
+            background_color: BackgroundColor(Color::BLACK.with_a(0.7)),
+            z_index: ZIndex::Global(i32::MAX),
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(12.),
+                ..default()
+            },
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "mutators",
+            TextStyle {
+                font_size: TITLE_FONT_SIZE,
+                color: Color::WHITE,
+                font: font.clone(),
+            },
+        ));
+        parent.spawn((
+            MultiplierLabel,
+            TextBundle::from_section(
+                multiplier_label(&match_settings),
+                TextStyle {
+                    font_size: ENTRY_FONT_SIZE,
+                    color: Color::WHITE,
+                    font: font.clone(),
+                },
+            ),
+        ));
+        let entries = Mutator::ALL.into_iter().map(Some).chain([None]);
+        for (index, entry) in entries.enumerate() {
+            let color = if index == 0 { SELECTED_COLOR } else { Color::WHITE };
+            parent.spawn((
+                MutatorEntry(entry),
+                TextBundle::from_section(
+                    entry_label(entry, &match_settings),
+                    TextStyle {
+                        font_size: ENTRY_FONT_SIZE,
+                        color,
+                        font: font.clone(),
+                    },
+                ),
+            ));
+        }
+    });
+}
+
+pub fn despawn_mutator_screen(mut commands: Commands, screen_query: Query<Entity, With<MutatorRoot>>) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// up and down pick a line, left, right and enter toggle the mutator, enter on back or backspace return to the menu
+pub fn navigate_mutators(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut mutator_selection: ResMut<MutatorSelection>,
+    mut match_settings: ResMut<MatchSettings>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let entries = Mutator::ALL.len() + 1;
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        mutator_selection.index = (mutator_selection.index + entries - 1) % entries;
+    }
+    if keyboard_input.just_pressed(KeyCode::Down) {
+        mutator_selection.index = (mutator_selection.index + 1) % entries;
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        next_state.set(AppState::Menu);
+        return;
+    }
+
+    if !keyboard_input.any_just_pressed([KeyCode::Left, KeyCode::Right, KeyCode::Return]) {
+        return;
+    }
+    match Mutator::ALL.get(mutator_selection.index) {
+        Some(mutator) => match_settings.mutators.toggle(*mutator),
+        None => {
+            if keyboard_input.just_pressed(KeyCode::Return) {
+                next_state.set(AppState::Menu);
+            }
+        },
+    }
+}
+
+pub fn update_mutator_entries(
+    mutator_selection: Res<MutatorSelection>,
+    match_settings: Res<MatchSettings>,
+    mut entry_query: Query<(&mut Text, &MutatorEntry), Without<MultiplierLabel>>,
+    mut multiplier_query: Query<&mut Text, With<MultiplierLabel>>,
+) {
+    if !mutator_selection.is_changed() && !match_settings.is_changed() {
+        return;
+    }
+
+    let selected = Mutator::ALL.get(mutator_selection.index).copied();
+    for (mut text, entry) in entry_query.iter_mut() {
+        text.sections[0].value = entry_label(entry.0, &match_settings);
+        text.sections[0].style.color = if entry.0 == selected { SELECTED_COLOR } else { Color::WHITE };
+    }
+    for mut text in multiplier_query.iter_mut() {
+        text.sections[0].value = multiplier_label(&match_settings);
+    }
+}
+
+// the mutators go on top of the rules of the game mode
+pub fn apply_mutators(
+    mut run_rules: ResMut<RunRules>,
+    match_settings: Res<MatchSettings>,
+) {
+    match_settings.mutators.apply(&mut run_rules);
+}
+
+// the whole simulation runs faster, the fixed update catches up with the virtual time.
+// a replay is watched at its own speed on top of it
+pub fn set_game_speed(
+    mut virtual_time: ResMut<Time<Virtual>>,
+    run_rules: Res<RunRules>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    let playback_speed = playback.map_or(1., |playback| playback.speed);
+    virtual_time.set_relative_speed(run_rules.game_speed * playback_speed);
+}
+
+pub fn enemies_hidden(run_rules: Res<RunRules>) -> bool {
+    run_rules.enemies_hidden
+}
+
+// only changes what is drawn, the enemies keep colliding while hidden
+pub fn flash_enemies(
+    mut ufo_query: Query<(&Ufo, &mut Visibility)>,
+    time: Res<Time<Fixed>>,
+) {
+    let visible = time.elapsed_seconds() % FLASH_PERIOD < FLASH_DURATION;
+    for (ufo, mut visibility) in ufo_query.iter_mut() {
+        if let UfoType::Enemy = ufo.kind {
+            let wanted = if visible { Visibility::Inherited } else { Visibility::Hidden };
+            if *visibility != wanted {
+                *visibility = wanted;
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+pub mod components;
+pub mod protocol;
+pub mod resources;
+pub mod systems;
+
+use std::time::Duration;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, OnEnter, OnExit, in_state},
+    app::{Startup, PreUpdate, Update, PostUpdate, FixedUpdate, RunFixedUpdateLoop},
+    time::run_fixed_update_schedule,
+};
+
+use crate::{
+    replay::systems::record_tick_input,
+    simulation::{rollback::save_snapshot, systems::{begin_tick, end_tick}, SimulationSet},
+    systems::{cli_arg, AppState},
+};
+
+use self::{
+    resources::{NetRole, NetSettings},
+    systems::{
+        netplay_active, enter_lobby, open_session, despawn_lobby, connect_peer, receive_inputs, send_inputs,
+        apply_net_input, record_checksum, roll_back, record_game_over, end_confirmed_match
+    },
+};
+
+// two players on two machines, each simulating the whole match. only the input goes over the network,
+// a late input is guessed and the simulation rolls back when the guess was wrong,
+// so the match only ends once the tick it ended on is confirmed by the input of both players.
+// `--host <port>` or `--join <address>` go straight to the lobby,
+// `--net-loss <chance>` and `--net-delay <ms>` make the connection worse on purpose
+pub struct NetplayPlug;
+
+impl Plugin for NetplayPlug {
+    fn build(&self, app: &mut App) {
+        let mut settings = NetSettings::default();
+        if let Some(port) = cli_arg::<u16>("--host") {
+            settings.role = NetRole::Host;
+            settings.address = format!("0.0.0.0:{port}");
+        } else if let Some(address) = cli_arg::<String>("--join") {
+            settings.role = NetRole::Join;
+            settings.address = address;
+        }
+        settings.conditions.loss = cli_arg::<f32>("--net-loss").unwrap_or(0.).clamp(0., 1.);
+        settings.conditions.delay = Duration::from_millis(cli_arg::<u64>("--net-delay").unwrap_or(0));
+
+        if settings.role != NetRole::Offline {
+            app.add_systems(Startup, enter_lobby);
+        }
+
+        app.insert_resource(settings)
+            .add_systems(OnEnter(AppState::Lobby), open_session)
+            .add_systems(OnExit(AppState::Lobby), despawn_lobby)
+            .add_systems(Update, connect_peer.run_if(netplay_active).run_if(in_state(AppState::Lobby)))
+            .add_systems(PreUpdate, receive_inputs.run_if(netplay_active).run_if(in_state(AppState::Playing)))
+            .add_systems(PostUpdate, send_inputs.run_if(netplay_active).run_if(in_state(AppState::Playing)))
+            .add_systems(RunFixedUpdateLoop, roll_back.before(run_fixed_update_schedule).run_if(netplay_active).run_if(in_state(AppState::Playing)))
+            .add_systems(FixedUpdate, record_checksum.after(save_snapshot).in_set(SimulationSet::Snapshot).run_if(netplay_active))
+            .add_systems(FixedUpdate, record_game_over.before(end_tick).in_set(SimulationSet::End).run_if(netplay_active))
+            .add_systems(Update, end_confirmed_match.run_if(netplay_active))
+            .add_systems(FixedUpdate, apply_net_input.after(begin_tick).before(record_tick_input).in_set(SimulationSet::Begin).run_if(netplay_active));
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{player::resources::MatchSettings, simulation::resources::{PlayerInput, TOGGLE_SHOOTER}};
+
+#[derive(Serialize, Deserialize)]
+pub enum NetMessage {
+    // sent by the joining player until the host answers
+    Hello,
+    // everything the joining player needs to simulate the same match
+    Welcome {
+        seed: u64,
+        tick_rate: f64,
+        match_settings: MatchSettings,
+    },
+    // input of the ticks from `start` on, until the peer acknowledges them.
+    // `ack` counts the ticks received in a row from the peer,
+    // `checksum` is (tick, state) of the latest tick both sides agree on
+    Inputs {
+        start: u32,
+        inputs: Vec<u8>,
+        ack: u32,
+        checksum: Option<(u32, u64)>,
+    },
+}
+
+impl NetMessage {
+    pub fn encode(&self) -> Option<Vec<u8>> {
+        ron::to_string(self).ok().map(String::into_bytes)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Option<NetMessage> {
+        ron::de::from_bytes(bytes).ok()
+    }
+}
+
+pub fn encode_player_input(input: PlayerInput, toggle_shooter: bool) -> u8 {
+    if toggle_shooter {
+        input.to_bits() | TOGGLE_SHOOTER
+    } else {
+        input.to_bits()
+    }
+}
+
+// a guess of the next input of the peer: it keeps holding the same buttons, but does not press new ones
+pub fn predict_input(last: u8) -> u8 {
+    let mut input = PlayerInput::from_bits(last);
+    input.dash = false;
+    input.to_bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Vec2;
+
+    use super::*;
+
+    #[test]
+    fn prediction_keeps_holding_the_buttons() {
+        let held = PlayerInput { movement: Vec2::new(-1., 1.), fire: true, ..Default::default() }.to_bits();
+        assert_eq!(predict_input(held), held);
+    }
+
+    #[test]
+    fn prediction_does_not_dash_again() {
+        let dash = PlayerInput { movement: Vec2::new(1., 0.), dash: true, ..Default::default() };
+        let predicted = PlayerInput::from_bits(predict_input(dash.to_bits()));
+        assert!(!predicted.dash);
+        assert_eq!(predicted.movement, dash.movement);
+    }
+
+    #[test]
+    fn prediction_drops_the_shooter_toggle() {
+        assert_eq!(predict_input(encode_player_input(PlayerInput::default(), true)), 0);
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    net::{SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+use super::protocol::{predict_input, NetMessage};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7777";
+// ticks between pressing a button and it being simulated, so the peer usually has it in time
+pub const INPUT_DELAY: u32 = 2;
+// ticks the simulation may run ahead of the peer on predicted input, it waits beyond that
+pub const MAX_PREDICTION: u32 = 8;
+// ticks of remote input kept past the last confirmed one. the peer can never be that far ahead,
+// anything beyond is a broken or forged packet and is dropped
+pub const REMOTE_INPUT_WINDOW: u32 = 256;
+const HELLO_INTERVAL: f32 = 0.5;
+const MAX_DATAGRAM: usize = 2048;
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum NetRole {
+    #[default]
+    Offline,
+    Host,
+    Join,
+}
+
+// packet loss and latency added on purpose, to test the netcode on one machine
+#[derive(Clone, Copy, Default)]
+pub struct NetConditions {
+    // chance of a packet being dropped, from 0 to 1
+    pub loss: f32,
+    pub delay: Duration,
+}
+
+#[derive(Resource)]
+pub struct NetSettings {
+    pub role: NetRole,
+    // the host listens on its port, the joining player connects to it
+    pub address: String,
+    pub conditions: NetConditions,
+}
+
+impl Default for NetSettings {
+    fn default() -> Self {
+        NetSettings {
+            role: NetRole::Offline,
+            address: DEFAULT_ADDRESS.to_string(),
+            conditions: NetConditions::default(),
+        }
+    }
+}
+
+impl NetSettings {
+    // the host only needs the port of the address
+    pub fn host_port(&self) -> Option<u16> {
+        self.address.rsplit(':').next()?.parse().ok()
+    }
+}
+
+// an online match in progress, or about to start in the lobby
+#[derive(Resource)]
+pub struct NetSession {
+    pub socket: UdpSocket,
+    pub peer: Option<SocketAddr>,
+    pub local_player: usize,
+    pub conditions: NetConditions,
+    // packets held back by the simulated latency
+    outbox: Vec<(Instant, Vec<u8>)>,
+    pub hello_timer: Timer,
+    // input of every tick, the local ones are known `INPUT_DELAY` ticks ahead
+    pub local_inputs: Vec<u8>,
+    pub remote_inputs: Vec<Option<u8>>,
+    // ticks of remote input received in a row, every tick before is final
+    pub confirmed_remote: u32,
+    // ticks of local input the peer has received in a row
+    pub peer_ack: u32,
+    // remote input every tick was simulated with, confirmed or predicted
+    pub simulated_remote: Vec<u8>,
+    // earliest tick simulated with a wrong prediction
+    pub rollback_to: Option<u32>,
+    // state of the simulation at the start of every tick, compared with the peer
+    pub checksums: Vec<u64>,
+    pub desynced: bool,
+    // the simulation is paused waiting for the peer
+    pub stalled: bool,
+    // tick the simulation ended on, the match only ends once that tick is confirmed
+    pub game_over_at: Option<u32>,
+}
+
+impl NetSession {
+    pub fn new(socket: UdpSocket, peer: Option<SocketAddr>, conditions: NetConditions) -> Self {
+        NetSession {
+            socket,
+            peer,
+            local_player: 0,
+            conditions,
+            outbox: Vec::new(),
+            hello_timer: Timer::from_seconds(HELLO_INTERVAL, TimerMode::Repeating),
+            // nobody can press anything before the first ticks
+            local_inputs: vec![0; INPUT_DELAY as usize],
+            remote_inputs: vec![Some(0); INPUT_DELAY as usize],
+            confirmed_remote: INPUT_DELAY,
+            peer_ack: 0,
+            simulated_remote: Vec::new(),
+            rollback_to: None,
+            checksums: Vec::new(),
+            desynced: false,
+            stalled: false,
+            game_over_at: None,
+        }
+    }
+
+    pub fn remote_player(&self) -> usize {
+        1 - self.local_player
+    }
+
+    pub fn send(&mut self, message: &NetMessage) {
+        let Some(bytes) = message.encode() else {
+            return;
+        };
+        if self.conditions.loss > 0. && thread_rng().gen::<f32>() < self.conditions.loss {
+            return;
+        }
+        self.outbox.push((Instant::now() + self.conditions.delay, bytes));
+    }
+
+    pub fn flush(&mut self) {
+        let Some(peer) = self.peer else {
+            return;
+        };
+        let now = Instant::now();
+        let socket = &self.socket;
+        self.outbox.retain(|(send_at, bytes)| {
+            if *send_at > now {
+                return true;
+            }
+            if let Err(err) = socket.send_to(bytes, peer) {
+                warn!("could not send to {peer}: {err}");
+            }
+            false
+        });
+    }
+
+    // every message waiting on the socket, with who sent it
+    pub fn receive(&mut self) -> Vec<(SocketAddr, NetMessage)> {
+        let mut messages = Vec::new();
+        let mut buffer = [0; MAX_DATAGRAM];
+        while let Ok((length, source)) = self.socket.recv_from(&mut buffer) {
+            if let Some(message) = NetMessage::decode(&buffer[..length]) {
+                messages.push((source, message));
+            }
+        }
+        messages
+    }
+
+    // store the remote input of the ticks from `start` on,
+    // and remember the earliest one that was simulated with a wrong guess.
+    // ticks sent again below the confirmed ones are skipped, ticks past the window are dropped
+    pub fn store_remote_inputs(&mut self, start: u32, inputs: &[u8]) {
+        let end = self.confirmed_remote.saturating_add(REMOTE_INPUT_WINDOW) as usize;
+        if start as usize >= end {
+            warn!("dropped remote input starting at tick {start}, too far ahead");
+            return;
+        }
+        for (tick, input) in (start as usize..end).zip(inputs.iter().copied()) {
+            if tick >= self.remote_inputs.len() {
+                self.remote_inputs.resize(tick + 1, None);
+            }
+            if self.remote_inputs[tick].is_some() {
+                continue;
+            }
+            self.remote_inputs[tick] = Some(input);
+            if self.simulated_remote.get(tick).is_some_and(|simulated| *simulated != input) {
+                let tick = tick as u32;
+                self.rollback_to = Some(self.rollback_to.map_or(tick, |rollback_to| rollback_to.min(tick)));
+            }
+        }
+        while self.remote_inputs.get(self.confirmed_remote as usize).is_some_and(Option::is_some) {
+            self.confirmed_remote += 1;
+        }
+    }
+
+    // the remote input of `tick`, or a guess if it has not arrived yet
+    pub fn remote_input(&self, tick: u32) -> u8 {
+        match self.remote_inputs.get(tick as usize).copied().flatten() {
+            Some(input) => input,
+            None => {
+                let last = self.remote_inputs[(self.confirmed_remote - 1) as usize].unwrap_or_default();
+                predict_input(last)
+            },
+        }
+    }
+
+    // the latest tick whose state both sides have simulated from confirmed input only
+    pub fn confirmed_checksum(&self) -> Option<(u32, u64)> {
+        let tick = self.confirmed_remote.min((self.checksums.len() as u32).checked_sub(1)?);
+        Some((tick, self.checksums[tick as usize]))
+    }
+
+    // whether the game over was simulated from confirmed input only, and will not be rolled back
+    pub fn game_over_confirmed(&self) -> bool {
+        self.game_over_at.is_some_and(|tick| {
+            tick < self.confirmed_remote && self.rollback_to.is_none_or(|rollback_to| rollback_to > tick)
+        })
+    }
+
+    // whether our state at the start of `tick` is final, so it can be compared with the peer
+    pub fn is_confirmed(&self, tick: u32) -> bool {
+        tick <= self.confirmed_remote
+            && (tick as usize) < self.checksums.len()
+            && self.rollback_to.is_none_or(|rollback_to| rollback_to >= tick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> NetSession {
+        let socket = UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+        NetSession::new(socket, None, NetConditions::default())
+    }
+
+    #[test]
+    fn inputs_in_a_row_are_confirmed() {
+        let mut net_session = session();
+        net_session.store_remote_inputs(INPUT_DELAY, &[1, 2, 3]);
+        assert_eq!(net_session.confirmed_remote, INPUT_DELAY + 3);
+        assert_eq!(net_session.remote_input(INPUT_DELAY + 1), 2);
+    }
+
+    #[test]
+    fn a_gap_holds_the_confirmed_ticks_back() {
+        let mut net_session = session();
+        net_session.store_remote_inputs(INPUT_DELAY + 2, &[5]);
+        assert_eq!(net_session.confirmed_remote, INPUT_DELAY);
+
+        net_session.store_remote_inputs(INPUT_DELAY, &[1, 1]);
+        assert_eq!(net_session.confirmed_remote, INPUT_DELAY + 3);
+    }
+
+    #[test]
+    fn inputs_sent_again_are_kept_as_first_received() {
+        let mut net_session = session();
+        net_session.store_remote_inputs(INPUT_DELAY, &[1, 2]);
+        net_session.store_remote_inputs(0, &[0, 0, 7, 7]);
+        assert_eq!(net_session.remote_input(INPUT_DELAY), 1);
+        assert_eq!(net_session.remote_input(INPUT_DELAY + 1), 2);
+    }
+
+    #[test]
+    fn a_wrong_guess_rolls_back_to_its_earliest_tick() {
+        let mut net_session = session();
+        net_session.simulated_remote = vec![0; INPUT_DELAY as usize + 4];
+        net_session.store_remote_inputs(INPUT_DELAY + 3, &[4]);
+        net_session.store_remote_inputs(INPUT_DELAY, &[0, 2]);
+        assert_eq!(net_session.rollback_to, Some(INPUT_DELAY + 1));
+    }
+
+    #[test]
+    fn a_right_guess_does_not_roll_back() {
+        let mut net_session = session();
+        net_session.simulated_remote = vec![3; INPUT_DELAY as usize + 2];
+        net_session.store_remote_inputs(INPUT_DELAY, &[3, 3]);
+        assert_eq!(net_session.rollback_to, None);
+    }
+
+    #[test]
+    fn ticks_past_the_window_are_dropped() {
+        let mut net_session = session();
+        net_session.store_remote_inputs(u32::MAX - 1, &[1]);
+        assert_eq!(net_session.remote_inputs.len(), INPUT_DELAY as usize);
+
+        net_session.store_remote_inputs(INPUT_DELAY + REMOTE_INPUT_WINDOW - 1, &[1, 1, 1]);
+        assert_eq!(net_session.remote_inputs.len(), (INPUT_DELAY + REMOTE_INPUT_WINDOW) as usize);
+    }
+
+    #[test]
+    fn missing_input_is_predicted_from_the_last_confirmed() {
+        let mut net_session = session();
+        net_session.store_remote_inputs(INPUT_DELAY, &[predict_input(0b1001)]);
+        assert_eq!(net_session.remote_input(INPUT_DELAY + 5), predict_input(0b1001));
+    }
+}
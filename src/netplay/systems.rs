@@ -0,0 +1,341 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    net::{SocketAddr, ToSocketAddrs, UdpSocket},
+};
+
+use crate::{
+    background::resources::GameMetadata,
+    player::resources::MatchSettings,
+    simulation::{
+        resources::{ActionInput, GameRng, PendingAssets, PlayerInput, SimulationSettings, SimulationTick, TickInput, TOGGLE_SHOOTER},
+        components::Interpolated,
+        rollback::{load_snapshot, Rollback, RollbackId, RollbackIds, Snapshots},
+    },
+    systems::{AppState, GameOver},
+};
+
+use super::{
+    components::LobbyRoot,
+    protocol::{encode_player_input, NetMessage},
+    resources::{NetRole, NetSession, NetSettings, INPUT_DELAY, MAX_PREDICTION},
+};
+
+use bevy::{app::AppExit, prelude::*};
+use rand::RngCore;
+
+const LOBBY_FONT_SIZE: f32 = 28.;
+// local input ticks sent again in every packet, until the peer acknowledges them
+const MAX_INPUTS_PER_PACKET: usize = 64;
+
+pub fn netplay_active(net_session: Option<Res<NetSession>>) -> bool {
+    net_session.is_some()
+}
+
+pub fn enter_lobby(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::Lobby);
+}
+
+fn bind_socket(net_settings: &NetSettings) -> std::io::Result<(UdpSocket, Option<SocketAddr>)> {
+    let (socket, peer) = match net_settings.role {
+        NetRole::Host => {
+            let port = net_settings.host_port().ok_or(std::io::ErrorKind::InvalidInput)?;
+            (UdpSocket::bind(("0.0.0.0", port))?, None)
+        },
+        _ => {
+            let peer = net_settings.address.to_socket_addrs()?.next().ok_or(std::io::ErrorKind::InvalidInput)?;
+            (UdpSocket::bind(("0.0.0.0", 0))?, Some(peer))
+        },
+    };
+    socket.set_nonblocking(true)?;
+    Ok((socket, peer))
+}
+
+pub fn open_session(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    net_settings: Res<NetSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    let (socket, peer) = match bind_socket(&net_settings) {
+        Ok(bound) => bound,
+        Err(err) => {
+            error!("could not open a connection on {}: {err}", net_settings.address);
+            next_state.set(AppState::Menu);
+            return;
+        },
+    };
+
+    let label = match net_settings.role {
+        NetRole::Host => format!("hosting on port {}\nwaiting for the other player", net_settings.host_port().unwrap_or_default()),
+        _ => format!("joining {}", net_settings.address),
+    };
+    commands.insert_resource(NetSession::new(socket, peer, net_settings.conditions));
+    commands.insert_resource(Snapshots::default());
+
+    commands.spawn((
+        LobbyRoot,
+        NodeBundle {
+            background_color: BackgroundColor(Color::BLACK.with_a(0.7)),
+            z_index: ZIndex::Global(i32::MAX),
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            label,
+            TextStyle {
+                font_size: LOBBY_FONT_SIZE,
+                color: Color::WHITE,
+                font: asset_server.load("BungeeSpice-Regular.ttf"),
+            },
+        ));
+    });
+}
+
+pub fn despawn_lobby(mut commands: Commands, lobby_query: Query<Entity, With<LobbyRoot>>) {
+    for entity in lobby_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// the joining player knocks until the host answers with the match to play
+#[allow(clippy::too_many_arguments)]
+pub fn connect_peer(
+    mut commands: Commands,
+    mut net_session: ResMut<NetSession>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut match_settings: ResMut<MatchSettings>,
+    net_settings: Res<NetSettings>,
+    game_rng: Res<GameRng>,
+    simulation_settings: Res<SimulationSettings>,
+    time: Res<Time<Real>>,
+) {
+    for (source, message) in net_session.receive() {
+        match (net_settings.role, message) {
+            (NetRole::Host, NetMessage::Hello) => {
+                match_settings.players = 2;
+                net_session.peer = Some(source);
+                net_session.local_player = 0;
+                net_session.send(&NetMessage::Welcome {
+                    seed: game_rng.seed,
+                    tick_rate: simulation_settings.tick_rate,
+                    match_settings: *match_settings,
+                });
+                info!("player 2 joined from {source}");
+                next_state.set(AppState::Playing);
+            },
+            (NetRole::Join, NetMessage::Welcome { seed, tick_rate, match_settings: host_settings }) => {
+                *match_settings = host_settings;
+                net_session.local_player = 1;
+                commands.insert_resource(GameRng::from_seed(seed));
+                commands.insert_resource(Time::<Fixed>::from_hz(tick_rate));
+                commands.insert_resource(SimulationSettings { tick_rate });
+                info!("joined the match as player 2, seed {seed}");
+                next_state.set(AppState::Playing);
+            },
+            _ => {},
+        }
+    }
+
+    if net_settings.role == NetRole::Join && net_session.hello_timer.tick(time.delta()).just_finished() {
+        net_session.send(&NetMessage::Hello);
+    }
+    net_session.flush();
+}
+
+pub fn receive_inputs(
+    mut net_session: ResMut<NetSession>,
+    net_settings: Res<NetSettings>,
+    game_rng: Res<GameRng>,
+    simulation_settings: Res<SimulationSettings>,
+    match_settings: Res<MatchSettings>,
+) {
+    for (source, message) in net_session.receive() {
+        if net_session.peer != Some(source) {
+            continue;
+        }
+        match message {
+            // the welcome got lost on the way
+            NetMessage::Hello if net_settings.role == NetRole::Host => {
+                net_session.send(&NetMessage::Welcome {
+                    seed: game_rng.seed,
+                    tick_rate: simulation_settings.tick_rate,
+                    match_settings: *match_settings,
+                });
+            },
+            NetMessage::Inputs { start, inputs, ack, checksum } => {
+                net_session.store_remote_inputs(start, &inputs);
+                net_session.peer_ack = net_session.peer_ack.max(ack);
+                if let Some((tick, peer_checksum)) = checksum {
+                    if net_session.is_confirmed(tick) && net_session.checksums[tick as usize] != peer_checksum && !net_session.desynced {
+                        error!("out of sync with the other player since tick {tick}");
+                        net_session.desynced = true;
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+pub fn send_inputs(mut net_session: ResMut<NetSession>) {
+    let start = net_session.peer_ack;
+    let end = net_session.local_inputs.len().min(start as usize + MAX_INPUTS_PER_PACKET);
+    let message = NetMessage::Inputs {
+        start,
+        inputs: net_session.local_inputs.get(start as usize..end).unwrap_or_default().to_vec(),
+        ack: net_session.confirmed_remote,
+        checksum: net_session.confirmed_checksum(),
+    };
+    net_session.send(&message);
+    net_session.flush();
+}
+
+// the local keyboard plays with the keys of the first player, whichever player it is online
+pub fn apply_net_input(
+    mut net_session: ResMut<NetSession>,
+    mut tick_input: ResMut<TickInput>,
+    simulation_tick: Res<SimulationTick>,
+) {
+    let tick = simulation_tick.0;
+    // ticks played again after a rollback already have their local input
+    if net_session.local_inputs.len() == (tick + INPUT_DELAY) as usize {
+        let local = encode_player_input(tick_input.players[0], tick_input.toggle_shooter);
+        net_session.local_inputs.push(local);
+    }
+
+    let local = net_session.local_inputs[tick as usize];
+    let remote = net_session.remote_input(tick);
+    net_session.simulated_remote.truncate(tick as usize);
+    net_session.simulated_remote.push(remote);
+
+    let (local_player, remote_player) = (net_session.local_player, net_session.remote_player());
+    *tick_input = TickInput::default();
+    tick_input.players[local_player] = PlayerInput::from_bits(local);
+    tick_input.players[remote_player] = PlayerInput::from_bits(remote);
+    tick_input.toggle_shooter = (local | remote) & TOGGLE_SHOOTER != 0;
+}
+
+// a cheap fingerprint of the simulated state, enough to notice when the peers drift apart
+pub fn record_checksum(
+    mut net_session: ResMut<NetSession>,
+    simulation_tick: Res<SimulationTick>,
+    game_rng: Res<GameRng>,
+    game_metadata: Res<GameMetadata>,
+    rollback_ids: Res<RollbackIds>,
+    rollback_query: Query<(&RollbackId, &Transform, Option<&Interpolated>), With<Rollback>>,
+) {
+    let mut hasher = DefaultHasher::new();
+    game_rng.rng.clone().next_u64().hash(&mut hasher);
+    game_metadata.level.to_bits().hash(&mut hasher);
+    for player in game_metadata.players.iter() {
+        (player.lifes, player.scores).hash(&mut hasher);
+    }
+    rollback_ids.next.hash(&mut hasher);
+
+    // the transform may still be drawn in between two ticks, the simulated translation is what both peers share
+    let mut translations: Vec<(RollbackId, [u32; 3])> = rollback_query.iter()
+        .map(|(id, transform, interpolated)| {
+            let translation = interpolated.map_or(transform.translation, |interpolated| interpolated.current);
+            (*id, translation.to_array().map(f32::to_bits))
+        })
+        .collect();
+    translations.sort_unstable_by_key(|(id, _)| *id);
+    for (id, translation) in translations {
+        (id.0, translation).hash(&mut hasher);
+    }
+
+    let tick = simulation_tick.0 as usize;
+    net_session.checksums.truncate(tick);
+    net_session.checksums.push(hasher.finish());
+}
+
+// a game over may come from a tick simulated with a wrong guess, it is only kept until a rollback says otherwise
+pub fn record_game_over(
+    mut game_over_events: EventReader<GameOver>,
+    mut net_session: ResMut<NetSession>,
+    simulation_tick: Res<SimulationTick>,
+) {
+    if game_over_events.read().count() > 0 && net_session.game_over_at.is_none() {
+        net_session.game_over_at = Some(simulation_tick.0);
+    }
+}
+
+pub fn end_confirmed_match(
+    net_session: Res<NetSession>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+) {
+    if net_session.game_over_confirmed() {
+        app_exit_event_writer.send(AppExit);
+    }
+}
+
+// runs before the fixed update of the frame: once a guess of the remote input turns out wrong,
+// the simulation goes back to that tick and plays every tick since again with the right input.
+// it also waits for the peer when it gets too far ahead of it
+pub fn roll_back(world: &mut World) {
+    let current = world.resource::<SimulationTick>().0;
+    if let Some(tick) = world.resource_mut::<NetSession>().rollback_to.take() {
+        if tick < current {
+            resimulate(world, tick, current);
+        }
+    }
+
+    // the assets hold the simulation on their own
+    if world.contains_resource::<PendingAssets>() {
+        return;
+    }
+    let mut net_session = world.resource_mut::<NetSession>();
+    let stall = current >= net_session.confirmed_remote + MAX_PREDICTION;
+    if stall == net_session.stalled {
+        return;
+    }
+    net_session.stalled = stall;
+    let mut virtual_time = world.resource_mut::<Time<Virtual>>();
+    if stall {
+        virtual_time.pause();
+    } else {
+        virtual_time.unpause();
+    }
+}
+
+fn resimulate(world: &mut World, from: u32, to: u32) {
+    if !load_snapshot(world, from) {
+        error!("cannot roll back to tick {from}, too far behind");
+        world.resource_mut::<NetSession>().desynced = true;
+        return;
+    }
+    // whatever the wrong guess ended up in is played again
+    let mut net_session = world.resource_mut::<NetSession>();
+    if net_session.game_over_at.is_some_and(|tick| tick >= from) {
+        net_session.game_over_at = None;
+    }
+    world.resource_mut::<Events<GameOver>>().clear();
+
+    // the frame input and fixed time are left as they were, only the ticks are played again
+    let fixed_time = *world.resource::<Time<Fixed>>();
+    let action_input = *world.resource::<ActionInput>();
+    let timestep = fixed_time.timestep();
+    world.schedule_scope(FixedUpdate, |world, schedule| {
+        for tick in from..to {
+            let mut tick_time = Time::<Fixed>::from_duration(timestep);
+            tick_time.advance_to(timestep * tick);
+            tick_time.advance_by(timestep);
+            world.insert_resource(tick_time);
+            *world.resource_mut::<Time>() = tick_time.as_generic();
+            schedule.run(world);
+        }
+    });
+    world.insert_resource(fixed_time);
+    let virtual_time = world.resource::<Time<Virtual>>().as_generic();
+    *world.resource_mut::<Time>() = virtual_time;
+    world.insert_resource(action_input);
+}
@@ -1,8 +1,10 @@
 use bevy::prelude::*;
-use rand::random;
+use rand::Rng;
 
-#[derive(Component)]
-pub struct UFO {
+use super::hitbox::{Hitbox, HitboxKind};
+
+#[derive(Component, Clone, Copy)]
+pub struct Ufo {
     pub fall_speed: f32,
     pub size: Vec3, 
     pub score: u32, // zero score mean enemy
@@ -14,39 +16,51 @@ pub const EXTRA_SPEED_PER_LV: f32 = 30.;
 pub const MIN_MODIFIER: f32 = 0.5;
 pub const MAX_MODIFIER: f32 = 1.5;
 
+#[derive(Clone, Copy)]
 pub enum UfoType {
-    ENEMY,
-    BONUS,
+    Enemy,
+    Bonus,
 }
 
 trait RandomFreeFall {
-    fn get_speed_modifier(max: f32, min: f32) -> f32 {
-        random::<f32>() * (max - min) + min
+    fn get_speed_modifier(rng: &mut impl Rng, max: f32, min: f32) -> f32 {
+        rng.gen::<f32>() * (max - min) + min
     }
 }
 
-impl RandomFreeFall for UFO {}
+impl RandomFreeFall for Ufo {}
+
+impl Ufo {
+    pub fn new(level: f32, width: f32, height: f32, score: u32, kind: UfoType, rng: &mut impl Rng) -> Self {
+        let modifier = Self::get_speed_modifier(rng, MAX_MODIFIER, MIN_MODIFIER);
+        Ufo {
+            score, kind,
+            fall_speed: Self::base_speed(level) * modifier,
+            size: Vec3::new(width, height, 0.),
+        }
+    }
 
-impl UFO {
-    pub fn new(level: f32, width: f32, height: f32, score: u32, kind: UfoType) -> Self {
-        let base_speed = ENEMY_SPEED + level * EXTRA_SPEED_PER_LV;
-        let modifier = Self::get_speed_modifier(MAX_MODIFIER, MIN_MODIFIER);
-        UFO {
+    // members of a formation share the same speed so the formation keeps its shape
+    pub fn steady(level: f32, width: f32, height: f32, score: u32, kind: UfoType) -> Self {
+        Ufo {
             score, kind,
-            fall_speed: base_speed * modifier,
+            fall_speed: Self::base_speed(level),
             size: Vec3::new(width, height, 0.),
         }
     }
+
+    pub fn base_speed(level: f32) -> f32 {
+        ENEMY_SPEED + level * EXTRA_SPEED_PER_LV
+    }
 }
 
 pub trait CollisionObject {
     fn get_dimension(&self) -> (f32, f32);
-    
-    fn min_distance(&self, target: & impl CollisionObject) -> (f32, f32) {
-        let (my_width, my_height) = self.get_dimension();
-        let (target_width, target_height) = target.get_dimension();
 
-        ((my_width + target_width) / 2., (my_height + target_height) / 2.) 
+    // the full sprite, unless the object knows its own shape
+    fn get_hitbox(&self) -> Hitbox {
+        let (width, height) = self.get_dimension();
+        Hitbox::rect(width, height)
     }
 
     fn collide(
@@ -55,15 +69,37 @@ pub trait CollisionObject {
         target: & impl CollisionObject, 
         target_translation: Vec3
     ) -> bool {
-        let (min_x, min_y) = self.min_distance(target);
+        self.get_hitbox().intersects(curr_translation, &target.get_hitbox(), target_translation)
+    }
+}
 
-        (curr_translation.x - target_translation.x).abs() < min_x
-        && (curr_translation.y - target_translation.y).abs() < min_y
+// objects whose hitbox is derived from the hitbox settings of their type
+pub trait HitboxSource: CollisionObject {
+    fn hitbox_kind(&self) -> HitboxKind;
+}
+
+impl CollisionObject for Hitbox {
+    fn get_dimension(&self) -> (f32, f32) {
+        let extents = self.extents();
+        (extents.x, extents.y)
+    }
+
+    fn get_hitbox(&self) -> Hitbox {
+        *self
     }
 }
 
-impl CollisionObject for UFO {
+impl CollisionObject for Ufo {
     fn get_dimension(&self) -> (f32, f32) {
         (self.size.x, self.size.y)
     }
 }
+
+impl HitboxSource for Ufo {
+    fn hitbox_kind(&self) -> HitboxKind {
+        match self.kind {
+            UfoType::Enemy => HitboxKind::Enemy,
+            UfoType::Bonus => HitboxKind::Bonus,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use super::components::UfoType;
+
+// an ufo left the game, either ran into or out of the playfield
+#[derive(Event)]
+pub struct UfoDespawnEvent {
+    pub translation: Vec3,
+    pub kind: UfoType,
+    pub collided: bool,
+}
@@ -0,0 +1,366 @@
+use bevy::{prelude::*, render::render_resource::TextureFormat};
+use serde::Deserialize;
+
+// pixels with less alpha than this are see-through and never collide
+const ALPHA_THRESHOLD: u8 = 128;
+
+// shape used for collision, relative to the translation of its entity
+#[derive(Component, Clone, Copy)]
+pub enum Hitbox {
+    Rect { half_size: Vec2, offset: Vec2 },
+    Circle { radius: f32, offset: Vec2 },
+    // vertical segment of 2 * half_length, inflated by radius
+    Capsule { radius: f32, half_length: f32, offset: Vec2 },
+}
+
+#[derive(Clone, Copy)]
+pub enum HitboxKind {
+    Character,
+    Enemy,
+    Bonus,
+    Boss,
+    Projectile,
+}
+
+// how the hitbox of an object type is derived from its sprite
+#[derive(Clone, Copy, PartialEq, Deserialize)]
+pub enum HitboxConfig {
+    // the full sprite, shrunk by the given margins on each side
+    Inset { x: f32, y: f32 },
+    // a circle fitting the smaller side of the sprite, scaled down by the given ratio
+    Circle { scale: f32 },
+    // a vertical capsule as tall as the sprite and as wide as the given ratio of it
+    Capsule { scale: f32 },
+    // the opaque part of the sprite, shrunk by the given margin on each side
+    AlphaMask { inset: f32 },
+}
+
+// hitboxes are made of rectangles and circles, so every pair of shapes
+// comes down to rect-rect, circle-rect and circle-circle tests
+#[derive(Clone, Copy)]
+enum Primitive {
+    Rect { center: Vec2, half_size: Vec2 },
+    Circle { center: Vec2, radius: f32 },
+}
+
+impl Primitive {
+    fn intersects(&self, other: &Primitive) -> bool {
+        match (*self, *other) {
+            (Primitive::Rect { center: c1, half_size: h1 }, Primitive::Rect { center: c2, half_size: h2 }) => {
+                (c1.x - c2.x).abs() < h1.x + h2.x && (c1.y - c2.y).abs() < h1.y + h2.y
+            },
+            (Primitive::Circle { center, radius }, Primitive::Rect { center: rect_center, half_size })
+            | (Primitive::Rect { center: rect_center, half_size }, Primitive::Circle { center, radius }) => {
+                let closest = center.clamp(rect_center - half_size, rect_center + half_size);
+                closest.distance_squared(center) < radius * radius
+            },
+            (Primitive::Circle { center: c1, radius: r1 }, Primitive::Circle { center: c2, radius: r2 }) => {
+                c1.distance_squared(c2) < (r1 + r2) * (r1 + r2)
+            },
+        }
+    }
+
+    // whether the primitive touches the other one while moving by `delta`.
+    // the moving one shrinks to a point travelling a segment, and the other one grows by its shape
+    fn swept_intersects(&self, delta: Vec2, other: &Primitive) -> bool {
+        match (*self, *other) {
+            (Primitive::Rect { center: c1, half_size: h1 }, Primitive::Rect { center: c2, half_size: h2 }) => {
+                segment_hits_rect(c1, delta, c2, h1 + h2)
+            },
+            (Primitive::Circle { center, radius }, Primitive::Rect { center: rect_center, half_size }) => {
+                segment_hits_rounded_rect(center, delta, rect_center, half_size, radius)
+            },
+            // the rect standing still and the circle moving the other way is the same motion
+            (Primitive::Rect { center: rect_center, half_size }, Primitive::Circle { center, radius }) => {
+                segment_hits_rounded_rect(center, -delta, rect_center, half_size, radius)
+            },
+            (Primitive::Circle { center: c1, radius: r1 }, Primitive::Circle { center: c2, radius: r2 }) => {
+                segment_hits_circle(c1, delta, c2, r1 + r2)
+            },
+        }
+    }
+}
+
+// the segment from `start` to `start + delta` against the inside of a rectangle, per axis
+fn segment_hits_rect(start: Vec2, delta: Vec2, center: Vec2, half_size: Vec2) -> bool {
+    let (mut t_enter, mut t_exit) = (0f32, 1f32);
+    for axis in 0..2 {
+        if delta[axis].abs() < f32::EPSILON {
+            if (start[axis] - center[axis]).abs() >= half_size[axis] {
+                return false;
+            }
+            continue;
+        }
+
+        let t0 = (center[axis] - half_size[axis] - start[axis]) / delta[axis];
+        let t1 = (center[axis] + half_size[axis] - start[axis]) / delta[axis];
+        t_enter = t_enter.max(t0.min(t1));
+        t_exit = t_exit.min(t0.max(t1));
+        if t_enter >= t_exit {
+            return false;
+        }
+    }
+    true
+}
+
+fn segment_hits_circle(start: Vec2, delta: Vec2, center: Vec2, radius: f32) -> bool {
+    let length_squared = delta.length_squared();
+    let t = if length_squared < f32::EPSILON {
+        0.
+    } else {
+        ((center - start).dot(delta) / length_squared).clamp(0., 1.)
+    };
+    (start + delta * t).distance_squared(center) < radius * radius
+}
+
+// a rectangle inflated by a radius is two crossed rectangles and a circle on every corner
+fn segment_hits_rounded_rect(start: Vec2, delta: Vec2, center: Vec2, half_size: Vec2, radius: f32) -> bool {
+    let corners = [Vec2::new(1., 1.), Vec2::new(-1., 1.), Vec2::new(1., -1.), Vec2::new(-1., -1.)];
+    segment_hits_rect(start, delta, center, half_size + Vec2::new(radius, 0.))
+        || segment_hits_rect(start, delta, center, half_size + Vec2::new(0., radius))
+        || corners.iter().any(|corner| segment_hits_circle(start, delta, center + *corner * half_size, radius))
+}
+
+impl Hitbox {
+    pub fn rect(width: f32, height: f32) -> Self {
+        Hitbox::Rect {
+            half_size: Vec2::new(width, height) / 2.,
+            offset: Vec2::ZERO,
+        }
+    }
+
+    pub fn from_config(config: HitboxConfig, size: Vec2, alpha_bounds: Option<Rect>) -> Self {
+        match config {
+            HitboxConfig::Inset { x, y } => Hitbox::Rect {
+                half_size: (size / 2. - Vec2::new(x, y)).max(Vec2::ZERO),
+                offset: Vec2::ZERO,
+            },
+            HitboxConfig::Circle { scale } => Hitbox::Circle {
+                radius: size.min_element() / 2. * scale,
+                offset: Vec2::ZERO,
+            },
+            HitboxConfig::Capsule { scale } => {
+                let radius = size.x / 2. * scale;
+                Hitbox::Capsule {
+                    radius,
+                    half_length: (size.y / 2. - radius).max(0.),
+                    offset: Vec2::ZERO,
+                }
+            },
+            HitboxConfig::AlphaMask { inset } => match alpha_bounds {
+                // bounds are given as fractions of the sprite size
+                Some(bounds) => Hitbox::Rect {
+                    half_size: (bounds.half_size() * size - Vec2::splat(inset)).max(Vec2::ZERO),
+                    offset: bounds.center() * size,
+                },
+                None => Hitbox::rect(size.x, size.y),
+            },
+        }
+    }
+
+    // smallest rectangle around the hitbox, as (width, height)
+    pub fn extents(&self) -> Vec2 {
+        match *self {
+            Hitbox::Rect { half_size, .. } => half_size * 2.,
+            Hitbox::Circle { radius, .. } => Vec2::splat(radius * 2.),
+            Hitbox::Capsule { radius, half_length, .. } => Vec2::new(radius, radius + half_length) * 2.,
+        }
+    }
+
+    // smallest rectangle around the hitbox once placed at the given translation
+    pub fn bounds(&self, translation: Vec3) -> Rect {
+        let offset = match *self {
+            Hitbox::Rect { offset, .. } | Hitbox::Circle { offset, .. } | Hitbox::Capsule { offset, .. } => offset,
+        };
+        Rect::from_center_size(translation.truncate() + offset, self.extents())
+    }
+
+    // bounds covering the whole path travelled between two translations
+    pub fn swept_bounds(&self, from: Vec3, to: Vec3) -> Rect {
+        self.bounds(from).union(self.bounds(to))
+    }
+
+    // continuous version of `intersects` for objects moving from one translation to another during a frame,
+    // so fast objects cannot step over each other between two frames.
+    // every pair of primitives is swept against each other, each one exactly
+    pub fn swept_intersects(
+        &self,
+        from: Vec3,
+        to: Vec3,
+        other: &Hitbox,
+        other_from: Vec3,
+        other_to: Vec3,
+    ) -> bool {
+        // motion of this hitbox as seen from the other one
+        let delta = ((to - from) - (other_to - other_from)).truncate();
+        let others = other.primitives(other_from);
+        self.primitives(from).iter().flatten().any(|mine| {
+            others.iter().flatten().any(|theirs| mine.swept_intersects(delta, theirs))
+        })
+    }
+
+    fn primitives(&self, translation: Vec3) -> [Option<Primitive>; 3] {
+        match *self {
+            Hitbox::Rect { half_size, offset } => [
+                Some(Primitive::Rect { center: translation.truncate() + offset, half_size }),
+                None,
+                None,
+            ],
+            Hitbox::Circle { radius, offset } => [
+                Some(Primitive::Circle { center: translation.truncate() + offset, radius }),
+                None,
+                None,
+            ],
+            Hitbox::Capsule { radius, half_length, offset } => {
+                let center = translation.truncate() + offset;
+                [
+                    Some(Primitive::Rect { center, half_size: Vec2::new(radius, half_length) }),
+                    Some(Primitive::Circle { center: center + Vec2::new(0., half_length), radius }),
+                    Some(Primitive::Circle { center: center - Vec2::new(0., half_length), radius }),
+                ]
+            },
+        }
+    }
+
+    pub fn intersects(&self, translation: Vec3, other: &Hitbox, other_translation: Vec3) -> bool {
+        let others = other.primitives(other_translation);
+        self.primitives(translation).iter().flatten().any(|mine| {
+            others.iter().flatten().any(|theirs| mine.intersects(theirs))
+        })
+    }
+}
+
+// bounds of the opaque pixels of an image, as fractions of its size centered on 0 with y up
+pub fn alpha_bounds(image: &Image) -> Option<Rect> {
+    match image.texture_descriptor.format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {},
+        _ => return None,
+    }
+
+    let (width, height) = (image.width(), image.height());
+    let mut min = UVec2::MAX;
+    let mut max = UVec2::ZERO;
+    for y in 0..height {
+        for x in 0..width {
+            let alpha = image.data[((y * width + x) * 4 + 3) as usize];
+            if alpha >= ALPHA_THRESHOLD {
+                min = min.min(UVec2::new(x, y));
+                max = max.max(UVec2::new(x, y));
+            }
+        }
+    }
+
+    if min.x > max.x || min.y > max.y {
+        return None;
+    }
+
+    let size = image.size_f32();
+    Some(Rect::from_corners(
+        Vec2::new(min.x as f32 / size.x - 0.5, 0.5 - (max.y + 1) as f32 / size.y),
+        Vec2::new((max.x + 1) as f32 / size.x - 0.5, 0.5 - min.y as f32 / size.y),
+    ))
+}
+
+// bounds of a mask as drawn, once the sprite is flipped on either axis
+pub fn flip_alpha_bounds(bounds: Rect, flip_x: bool, flip_y: bool) -> Rect {
+    let sign = Vec2::new(if flip_x { -1. } else { 1. }, if flip_y { -1. } else { 1. });
+    Rect::from_corners(bounds.min * sign, bounds.max * sign)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle(radius: f32) -> Hitbox {
+        Hitbox::Circle { radius, offset: Vec2::ZERO }
+    }
+
+    fn at(x: f32, y: f32) -> Vec3 {
+        Vec3::new(x, y, 0.)
+    }
+
+    #[test]
+    fn a_flipped_sprite_flips_its_mask() {
+        // opaque in the top right quarter of the image
+        let bounds = Rect::new(0., 0., 0.5, 0.5);
+        let config = HitboxConfig::AlphaMask { inset: 0. };
+        let size = Vec2::new(40., 60.);
+
+        let Hitbox::Rect { offset, .. } = Hitbox::from_config(config, size, Some(bounds)) else {
+            panic!("an alpha mask makes a rectangle");
+        };
+        assert_eq!(offset, Vec2::new(10., 15.));
+
+        let flipped = flip_alpha_bounds(bounds, false, true);
+        let Hitbox::Rect { offset, half_size } = Hitbox::from_config(config, size, Some(flipped)) else {
+            panic!("an alpha mask makes a rectangle");
+        };
+        assert_eq!(offset, Vec2::new(10., -15.));
+        assert_eq!(half_size, Vec2::new(10., 15.));
+
+        let flipped = flip_alpha_bounds(bounds, true, true);
+        let Hitbox::Rect { offset, .. } = Hitbox::from_config(config, size, Some(flipped)) else {
+            panic!("an alpha mask makes a rectangle");
+        };
+        assert_eq!(offset, Vec2::new(-10., -15.));
+    }
+
+    #[test]
+    fn a_fast_object_cannot_step_over_another() {
+        let (projectile, ufo) = (Hitbox::rect(8., 20.), Hitbox::rect(50., 50.));
+        assert!(!projectile.intersects(at(0., -100.), &ufo, at(0., 0.)));
+        assert!(!projectile.intersects(at(0., 100.), &ufo, at(0., 0.)));
+        assert!(projectile.swept_intersects(at(0., -100.), at(0., 100.), &ufo, at(0., 0.), at(0., 0.)));
+    }
+
+    #[test]
+    fn both_objects_moving_count() {
+        let (projectile, ufo) = (Hitbox::rect(8., 20.), Hitbox::rect(50., 50.));
+        assert!(projectile.swept_intersects(at(0., -100.), at(0., 0.), &ufo, at(0., 100.), at(0., -20.)));
+        assert!(!projectile.swept_intersects(at(0., -100.), at(0., 0.), &ufo, at(0., 200.), at(0., 100.)));
+    }
+
+    #[test]
+    fn passing_by_a_corner_is_a_miss() {
+        // the bounding boxes overlap on the way, the circle never reaches the rectangle
+        let ufo = Hitbox::rect(20., 20.);
+        assert!(!circle(10.).swept_intersects(at(-1., 39.), at(39., -1.), &ufo, at(0., 0.), at(0., 0.)));
+        assert!(!ufo.swept_intersects(at(0., 0.), at(0., 0.), &circle(10.), at(-1., 39.), at(39., -1.)));
+    }
+
+    #[test]
+    fn clipping_a_corner_is_a_hit() {
+        let ufo = Hitbox::rect(20., 20.);
+        assert!(circle(10.).swept_intersects(at(-5., 35.), at(35., -5.), &ufo, at(0., 0.), at(0., 0.)));
+        assert!(ufo.swept_intersects(at(0., 0.), at(0., 0.), &circle(10.), at(-5., 35.), at(35., -5.)));
+    }
+
+    #[test]
+    fn crossing_circles_meet_on_the_way() {
+        assert!(circle(5.).swept_intersects(at(-50., 0.), at(50., 0.), &circle(5.), at(0., 8.), at(0., 8.)));
+        assert!(!circle(5.).swept_intersects(at(-50., 0.), at(50., 0.), &circle(5.), at(0., 12.), at(0., 12.)));
+    }
+
+    #[test]
+    fn a_capsule_hits_with_its_round_end() {
+        let character = Hitbox::Capsule { radius: 10., half_length: 20., offset: Vec2::ZERO };
+        let ufo = Hitbox::rect(20., 20.);
+        // falling on the top cap, its corner is cut off
+        assert!(ufo.swept_intersects(at(0., 100.), at(0., 0.), &character, at(0., 0.), at(0., 0.)));
+        assert!(!ufo.swept_intersects(at(19., 100.), at(19., 35.), &character, at(0., 0.), at(0., 0.)));
+    }
+
+    #[test]
+    fn standing_still_is_a_plain_intersection() {
+        let ufo = Hitbox::rect(20., 20.);
+        assert!(circle(10.).swept_intersects(at(15., 0.), at(15., 0.), &ufo, at(0., 0.), at(0., 0.)));
+        assert!(!circle(10.).swept_intersects(at(25., 0.), at(25., 0.), &ufo, at(0., 0.), at(0., 0.)));
+    }
+
+    #[test]
+    fn offsets_move_the_shape() {
+        let shifted = Hitbox::Rect { half_size: Vec2::splat(10.), offset: Vec2::new(100., 0.) };
+        assert!(!circle(5.).swept_intersects(at(0., -50.), at(0., 50.), &shifted, at(0., 0.), at(0., 0.)));
+        assert!(circle(5.).swept_intersects(at(100., -50.), at(100., 50.), &shifted, at(0., 0.), at(0., 0.)));
+    }
+}
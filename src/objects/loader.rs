@@ -0,0 +1,23 @@
+use crate::data::RonAsset;
+
+use super::{hitbox::HitboxConfig, resources::HitboxSettings};
+
+impl RonAsset for HitboxSettings {
+    const NAME: &'static str = "hitbox settings";
+    const EXTENSIONS: &'static [&'static str] = &["hitboxes.ron"];
+
+    fn validate(&mut self) -> Result<(), String> {
+        // a hitbox scaled past its sprite would hit what cannot be seen
+        for config in [self.character, self.enemy, self.bonus, self.boss, self.projectile] {
+            let valid = match config {
+                HitboxConfig::Inset { x, y } => x >= 0. && y >= 0.,
+                HitboxConfig::Circle { scale } | HitboxConfig::Capsule { scale } => (0. ..=1.).contains(&scale),
+                HitboxConfig::AlphaMask { inset } => inset >= 0.,
+            };
+            if !valid {
+                return Err("insets must not be negative and scales must be between 0 and 1".to_string());
+            }
+        }
+        Ok(())
+    }
+}
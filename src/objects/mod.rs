@@ -1,21 +1,54 @@
 pub mod components;
+pub mod events;
+pub mod hitbox;
+pub mod loader;
 pub mod resources;
 pub mod systems;
 
-use bevy::{prelude::{Plugin, App}, app::{Startup, Update}};
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, Condition, AssetEvent, not, resource_exists, on_event},
+    app::{Startup, Update, FixedUpdate},
+};
 
-use self::{systems::{spawn_enemy_overtime, init_enemy_texture, tick_enemy_spawn_timer, ufo_fall, ufo_cleanup, tick_bonus_spawn_timer, init_bonus_texture, spawn_bonus_overtime}, resources::{EnemySpawnTimer, BonusObjectSpawnTimer}};
+use crate::{
+    boss::systems::no_boss, collision::{CollisionSet, stress::stress_test_running}, data::RonAssetApp,
+    simulation::{SimulationSet, resources::PendingAssets, rollback::RollbackApp, systems::release_simulation},
+    versus::systems::versus_match, waves::systems::waves_unavailable
+};
+
+use self::{events::UfoDespawnEvent, systems::{load_hitbox_settings, apply_hitbox_settings, spawn_enemy_overtime, init_enemy_texture, tick_enemy_spawn_timer, ufo_fall, ufo_cleanup, tick_bonus_spawn_timer, init_bonus_texture, spawn_bonus_overtime, resolve_hitboxes, despawn_collided_ufo}, resources::{EnemySpawnTimer, BonusObjectSpawnTimer, HitboxSettings, AlphaMasks}, components::Ufo, hitbox::Hitbox};
 
 pub struct EnemyPlug;
 
 impl Plugin for EnemyPlug {
     fn build(&self, app: &mut App) {
         app.init_resource::<EnemySpawnTimer>()
+            .add_event::<UfoDespawnEvent>()
             .init_resource::<BonusObjectSpawnTimer>()
-            .add_systems(Startup, (init_enemy_texture, init_bonus_texture))
-            .add_systems(Update, (tick_enemy_spawn_timer, tick_bonus_spawn_timer))
-            .add_systems(Update, (spawn_enemy_overtime, spawn_bonus_overtime))
-            .add_systems(Update, ufo_fall)
-            .add_systems(Update, ufo_cleanup);
+            .init_ron_asset::<HitboxSettings>()
+            .init_resource::<HitboxSettings>()
+            .init_resource::<AlphaMasks>()
+            .rollback_component::<Ufo>()
+            .rollback_component::<Hitbox>()
+            .rollback_resource::<EnemySpawnTimer>()
+            .rollback_resource::<BonusObjectSpawnTimer>()
+            .add_systems(Startup, (load_hitbox_settings, init_enemy_texture, init_bonus_texture))
+            // kept up to date until the simulation starts, so the first objects are shaped by the file
+            .add_systems(Update, apply_hitbox_settings
+                .run_if(resource_exists::<PendingAssets>().or_else(on_event::<AssetEvent<HitboxSettings>>()))
+                .before(release_simulation))
+            .add_systems(FixedUpdate, (tick_enemy_spawn_timer, tick_bonus_spawn_timer))
+            // in versus every ufo is dropped by a player
+            .add_systems(FixedUpdate, spawn_enemy_overtime
+                .after(tick_enemy_spawn_timer)
+                .run_if(waves_unavailable)
+                .run_if(no_boss)
+                .run_if(not(versus_match))
+                .run_if(not(stress_test_running)))
+            .add_systems(FixedUpdate, spawn_bonus_overtime.after(tick_bonus_spawn_timer).run_if(not(versus_match)))
+            .add_systems(FixedUpdate, resolve_hitboxes::<Ufo>.in_set(SimulationSet::Begin))
+            .add_systems(FixedUpdate, ufo_fall.in_set(CollisionSet::Movement))
+            .add_systems(FixedUpdate, despawn_collided_ufo.in_set(CollisionSet::Reaction))
+            .add_systems(FixedUpdate, ufo_cleanup);
     }
 }
\ No newline at end of file
@@ -1,4 +1,7 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, reflect::TypePath, utils::HashMap};
+use serde::Deserialize;
+
+use super::hitbox::{HitboxConfig, HitboxKind};
 
 const ENEMY_DEFAULT_SPAWN_DURATION: f32 = 1.0;
 const BONUS_DEFAULT_SPAWN_DURATION: f32 = ENEMY_DEFAULT_SPAWN_DURATION * 2.;
@@ -13,7 +16,7 @@ pub struct BonusObjectTexture {
     pub handles: Vec<Handle<Image>>
 }
 
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct EnemySpawnTimer {
     pub timer: Timer
 }
@@ -26,7 +29,7 @@ impl Default for EnemySpawnTimer {
     }
 }
 
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct BonusObjectSpawnTimer {
     pub timer: Timer
 }
@@ -38,3 +41,48 @@ impl Default for BonusObjectSpawnTimer {
         }
     }
 }
+
+// set in `assets/data/default.hitboxes.ron`, these defaults stand in until it is loaded or when it cannot be
+#[derive(Resource, Asset, TypePath, Deserialize, Clone, Copy, PartialEq)]
+pub struct HitboxSettings {
+    pub character: HitboxConfig,
+    pub enemy: HitboxConfig,
+    pub bonus: HitboxConfig,
+    pub boss: HitboxConfig,
+    pub projectile: HitboxConfig,
+}
+
+impl HitboxSettings {
+    pub fn config(&self, kind: HitboxKind) -> HitboxConfig {
+        match kind {
+            HitboxKind::Character => self.character,
+            HitboxKind::Enemy => self.enemy,
+            HitboxKind::Bonus => self.bonus,
+            HitboxKind::Boss => self.boss,
+            HitboxKind::Projectile => self.projectile,
+        }
+    }
+}
+
+impl Default for HitboxSettings {
+    fn default() -> Self {
+        HitboxSettings {
+            character: HitboxConfig::Capsule { scale: 0.7 },
+            enemy: HitboxConfig::AlphaMask { inset: 4. },
+            bonus: HitboxConfig::AlphaMask { inset: 2. },
+            boss: HitboxConfig::Circle { scale: 0.9 },
+            projectile: HitboxConfig::Inset { x: 0., y: 0. },
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct HitboxSettingsHandle {
+    pub handle: Handle<HitboxSettings>,
+}
+
+// opaque bounds of every sprite used by an alpha mask hitbox, computed once per image
+#[derive(Resource, Default)]
+pub struct AlphaMasks {
+    pub bounds: HashMap<AssetId<Image>, Option<Rect>>,
+}
@@ -1,19 +1,26 @@
-use crate::background::{
-    resources::{
-        GameBonudary, GameMetadata
-    }, 
-    systems::WINDOW_WIDTH
+use crate::{
+    background::{
+        resources::{
+            GameBonudary, GameMetadata, RunRules
+        }, 
+        systems::WINDOW_WIDTH
+    },
+    collision::events::CollisionEvent,
+    shooter::{components::Projectile, systems::projectile_hits},
+    simulation::{resources::{GameRng, PendingAssets}, rollback::Rollback},
 };
 
 use super::{
-    components::{UFO, UfoType}, 
+    components::{Ufo, UfoType, HitboxSource}, 
+    events::UfoDespawnEvent,
+    hitbox::{alpha_bounds, flip_alpha_bounds, Hitbox, HitboxConfig},
     resources::{
-        EnemyTexture, EnemySpawnTimer, BonusObjectSpawnTimer, BonusObjectTexture
+        EnemyTexture, EnemySpawnTimer, BonusObjectSpawnTimer, BonusObjectTexture, HitboxSettings, HitboxSettingsHandle, AlphaMasks
     }
 };
 
-use bevy::prelude::*;
-use rand::random;
+use bevy::{prelude::*, utils::HashSet};
+use rand::Rng;
 
 pub const UFO_WIDTH: f32 = 51.0;
 pub const UFO_HEIGHT: f32 = 73.0;
@@ -21,11 +28,13 @@ pub const UFO_HEIGHT: f32 = 73.0;
 pub fn init_enemy_texture(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut pending_assets: ResMut<PendingAssets>,
 ) {
     let handles: Vec<Handle<Image>> = [
         asset_server.load("sprites/enemy/ghost_normal.png"),
         asset_server.load("sprites/enemy/ghost.png"),
     ].to_vec();
+    pending_assets.handles.extend(handles.iter().map(|handle| handle.clone().untyped()));
     
     commands.insert_resource(EnemyTexture { handles });
 }
@@ -33,16 +42,42 @@ pub fn init_enemy_texture(
 pub fn init_bonus_texture(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut pending_assets: ResMut<PendingAssets>,
 ) {
     let handles: Vec<Handle<Image>> = [
         asset_server.load("sprites/enemy/slime.png"),
         asset_server.load("sprites/enemy/slimeBlue.png"),
         asset_server.load("sprites/enemy/slimeGreen.png"),
     ].to_vec();
+    pending_assets.handles.extend(handles.iter().map(|handle| handle.clone().untyped()));
     
     commands.insert_resource(BonusObjectTexture { handles });
 }
 
+pub fn load_hitbox_settings(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pending_assets: ResMut<PendingAssets>,
+) {
+    let handle: Handle<HitboxSettings> = asset_server.load("data/default.hitboxes.ron");
+    pending_assets.handles.push(handle.clone().untyped());
+    commands.insert_resource(HitboxSettingsHandle { handle });
+}
+
+// objects keep the hitbox they were given, a change of the file only shapes the ones spawned after it
+pub fn apply_hitbox_settings(
+    mut hitbox_settings: ResMut<HitboxSettings>,
+    settings_assets: Res<Assets<HitboxSettings>>,
+    settings_handle: Res<HitboxSettingsHandle>,
+) {
+    let Some(settings) = settings_assets.get(&settings_handle.handle) else {
+        return;
+    };
+    if *hitbox_settings != *settings {
+        *hitbox_settings = *settings;
+    }
+}
+
 pub fn tick_enemy_spawn_timer(mut spawn_timer: ResMut<EnemySpawnTimer>, time: Res<Time>) {
     spawn_timer.timer.tick(time.delta());
 }
@@ -51,31 +86,53 @@ pub fn tick_bonus_spawn_timer(mut spawn_timer: ResMut<BonusObjectSpawnTimer>, ti
     spawn_timer.timer.tick(time.delta());
 }
 
-fn spawn_ufo(
-    mut commands: Commands,
+pub fn spawn_ufo_at(
+    commands: &mut Commands,
     texture: Handle<Image>,
+    translation: Vec3,
+    mut ufo: Ufo,
+    run_rules: &RunRules,
     game_boundary: &GameBonudary,
-    game_metadata: &GameMetadata,
-    score: u32,
-    kind: UfoType,
 ) {
+    ufo.size *= run_rules.ufo_scale;
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(
-                random::<f32>() * WINDOW_WIDTH + game_boundary.x_min,
-                game_boundary.y_max,
-                0.,
-            ),
+            transform: Transform::from_translation(run_rules.ufo_start(translation, ufo.size.y / 2., game_boundary))
+                .with_scale(Vec3::new(run_rules.ufo_scale, run_rules.ufo_scale, 1.)),
             texture,
+            sprite: Sprite {
+                flip_y: run_rules.fall_direction > 0.,
+                ..default()
+            },
             ..default()
         },
-        UFO::new(game_metadata.level, UFO_WIDTH, UFO_HEIGHT, score, kind),
+        ufo,
+        Rollback,
     ));
 }
 
-fn pick_texture(handles: &Vec<Handle<Image>>) -> Handle<Image> {
-    let texture_idx: usize = (handles.len() - 1) * (random::<f32>() as usize);
-    handles[texture_idx].clone()
+#[allow(clippy::too_many_arguments)]
+fn spawn_ufo(
+    mut commands: Commands,
+    texture: Handle<Image>,
+    game_boundary: &GameBonudary,
+    game_metadata: &GameMetadata,
+    run_rules: &RunRules,
+    score: u32,
+    kind: UfoType,
+    rng: &mut impl Rng,
+) {
+    let translation = Vec3::new(
+        rng.gen::<f32>() * WINDOW_WIDTH + game_boundary.x_min,
+        game_boundary.y_max,
+        0.,
+    );
+    let ufo = Ufo::new(game_metadata.level, UFO_WIDTH, UFO_HEIGHT, score, kind, rng);
+    spawn_ufo_at(&mut commands, texture, translation, ufo, run_rules, game_boundary);
+}
+
+pub fn pick_texture(handles: &[Handle<Image>], rng: &mut impl Rng) -> Handle<Image> {
+    handles[rng.gen_range(0..handles.len())].clone()
 }
 
 pub fn spawn_enemy_overtime(
@@ -84,11 +141,13 @@ pub fn spawn_enemy_overtime(
     spawn_timer: Res<EnemySpawnTimer>,
     game_boundary: Res<GameBonudary>,
     game_metadata: Res<GameMetadata>,
+    run_rules: Res<RunRules>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     if spawn_timer.timer.finished() {
         // spawn new
-        let selected_texture = pick_texture(&texture.handles);
-        spawn_ufo(commands, selected_texture, game_boundary.as_ref(), game_metadata.as_ref(), 0, UfoType::ENEMY);
+        let selected_texture = pick_texture(&texture.handles, &mut game_rng.rng);
+        spawn_ufo(commands, selected_texture, game_boundary.as_ref(), game_metadata.as_ref(), run_rules.as_ref(), 0, UfoType::Enemy, &mut game_rng.rng);
     }
 }
 
@@ -98,32 +157,106 @@ pub fn spawn_bonus_overtime(
     spawn_timer: Res<BonusObjectSpawnTimer>,
     game_boundary: Res<GameBonudary>,
     game_metadata: Res<GameMetadata>,
+    run_rules: Res<RunRules>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    if spawn_timer.timer.finished() {
+    if spawn_timer.timer.finished() && run_rules.bonuses {
         // spawn new
-        let selected_texture = pick_texture(&texture.handles);
-        spawn_ufo(commands, selected_texture, game_boundary.as_ref(), game_metadata.as_ref(), 10, UfoType::BONUS);
+        let selected_texture = pick_texture(&texture.handles, &mut game_rng.rng);
+        spawn_ufo(commands, selected_texture, game_boundary.as_ref(), game_metadata.as_ref(), run_rules.as_ref(), 10, UfoType::Bonus, &mut game_rng.rng);
     }
 }
 
 pub fn ufo_fall(
-    mut ufo_query: Query<(&mut Transform, & UFO)>,
+    mut ufo_query: Query<(&mut Transform, & Ufo)>,
+    run_rules: Res<RunRules>,
     time: Res<Time>,
 ) {
     for (mut transform, enemy ) in ufo_query.iter_mut() {
-        let direction = Vec3::new(0., -1., 0.);
-        transform.translation += direction * enemy.fall_speed * time.delta_seconds();
+        let direction = Vec3::new(0., run_rules.fall_direction, 0.);
+        let modifier = match enemy.kind {
+            UfoType::Enemy => run_rules.enemy_speed,
+            UfoType::Bonus => 1.,
+        };
+        transform.translation += direction * enemy.fall_speed * modifier * time.delta_seconds();
     }
 }
 
 pub fn ufo_cleanup(
     mut command: Commands,
-    ufo_query: Query<(Entity, &Transform), With<UFO>>,
+    mut ufo_despawn_event: EventWriter<UfoDespawnEvent>,
+    ufo_query: Query<(Entity, &Transform, &Ufo)>,
     game_boundary: Res<GameBonudary>,
+    run_rules: Res<RunRules>,
 ) {
-    for (entity, transform) in ufo_query.iter() {
-        if transform.translation.y < game_boundary.y_min {
+    for (entity, transform, ufo) in ufo_query.iter() {
+        if run_rules.ufo_gone(transform.translation, ufo.fall_speed, &game_boundary) {
             command.entity(entity).despawn();
+            ufo_despawn_event.send(UfoDespawnEvent {
+                translation: transform.translation,
+                kind: ufo.kind,
+                collided: false,
+            });
+        }
+    }
+}
+
+// an ufo is gone once a character runs into it, or once it takes the hit of a projectile
+pub fn despawn_collided_ufo(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut ufo_despawn_event: EventWriter<UfoDespawnEvent>,
+    ufo_query: Query<(&Transform, &Ufo)>,
+    projectile_query: Query<(), With<Projectile>>,
+) {
+    let events: Vec<&CollisionEvent> = collision_events.read().collect();
+    let is_projectile = |entity| projectile_query.contains(entity);
+    let character_hits = events.iter().copied().filter(|event| !is_projectile(event.a));
+    let shot_hits = projectile_hits(events.iter().copied(), is_projectile, |entity| ufo_query.contains(entity));
+
+    let mut despawned = HashSet::new();
+    for event in character_hits.chain(shot_hits) {
+        let Ok((transform, ufo)) = ufo_query.get(event.b) else {
+            continue;
+        };
+        if despawned.insert(event.b) {
+            commands.entity(event.b).despawn();
+            ufo_despawn_event.send(UfoDespawnEvent {
+                translation: transform.translation,
+                kind: ufo.kind,
+                collided: true,
+            });
+        }
+    }
+}
+
+// give new collision objects their hitbox, waiting for their sprite to load when it is needed
+#[allow(clippy::type_complexity)]
+pub fn resolve_hitboxes<T: Component + HitboxSource>(
+    mut commands: Commands,
+    mut alpha_masks: ResMut<AlphaMasks>,
+    images: Res<Assets<Image>>,
+    hitbox_settings: Res<HitboxSettings>,
+    object_query: Query<(Entity, &T, &Transform, &Sprite, &Handle<Image>), Without<Hitbox>>,
+) {
+    for (entity, object, transform, sprite, texture) in object_query.iter() {
+        let config = hitbox_settings.config(object.hitbox_kind());
+        let (width, height) = object.get_dimension();
+        let mut size = Vec2::new(width, height);
+        let mut bounds = None;
+
+        if let HitboxConfig::AlphaMask { .. } = config {
+            let Some(image) = images.get(texture) else {
+                continue;
+            };
+            // the mask is kept as the image holds it, the sprite may be drawn flipped
+            bounds = alpha_masks.bounds
+                .entry(texture.id())
+                .or_insert_with(|| alpha_bounds(image))
+                .map(|bounds| flip_alpha_bounds(bounds, sprite.flip_x, sprite.flip_y));
+            size = sprite.custom_size.unwrap_or(image.size_f32()) * transform.scale.truncate();
         }
+
+        commands.entity(entity).insert(Hitbox::from_config(config, size, bounds));
     }
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use super::resources::{EffectKind, ParticleEffect};
+
+// emits the particles of an effect where it stands, then goes away
+#[derive(Component)]
+pub struct Emitter {
+    pub kind: EffectKind,
+    pub age: f32,
+    pub emitted: u32,
+}
+
+// a free particle is hidden until an emitter picks it up again
+#[derive(Component)]
+pub struct Particle {
+    pub alive: bool,
+    pub velocity: Vec2,
+    pub gravity: f32,
+    pub age: f32,
+    pub lifetime: f32,
+    pub size: (f32, f32),
+    pub start_color: Vec4,
+    pub end_color: Vec4,
+}
+
+impl Particle {
+    pub fn new(effect: &ParticleEffect, rng: &mut impl Rng) -> Self {
+        let angle = (effect.direction + (rng.gen::<f32>() - 0.5) * effect.spread).to_radians();
+        let speed = rng.gen_range(effect.speed.0..=effect.speed.1);
+        Particle {
+            alive: true,
+            velocity: Vec2::from_angle(angle) * speed,
+            gravity: effect.gravity,
+            age: 0.,
+            lifetime: rng.gen_range(effect.lifetime.0..=effect.lifetime.1),
+            size: effect.size,
+            start_color: Vec4::from(effect.start_color),
+            end_color: Vec4::from(effect.end_color),
+        }
+    }
+
+    fn progress(&self) -> f32 {
+        (self.age / self.lifetime).clamp(0., 1.)
+    }
+
+    pub fn color(&self) -> Color {
+        Color::from(self.start_color.lerp(self.end_color, self.progress()))
+    }
+
+    pub fn size(&self) -> f32 {
+        self.size.0 + (self.size.1 - self.size.0) * self.progress()
+    }
+}
@@ -0,0 +1,22 @@
+use crate::data::RonAsset;
+
+use super::resources::ParticleEffectSet;
+
+fn valid_range((min, max): (f32, f32)) -> bool {
+    min >= 0. && min <= max
+}
+
+impl RonAsset for ParticleEffectSet {
+    const NAME: &'static str = "particle effects";
+    const EXTENSIONS: &'static [&'static str] = &["particles.ron"];
+
+    fn validate(&mut self) -> Result<(), String> {
+        // the values of a particle are drawn in these ranges
+        for (kind, effect) in self.effects.iter() {
+            if !valid_range(effect.lifetime) || effect.lifetime.0 <= 0. || !valid_range(effect.speed) {
+                return Err(format!("particle effect {kind:?} has an invalid range"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+pub mod components;
+pub mod loader;
+pub mod resources;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, not},
+    app::{Startup, Update, PostUpdate},
+};
+
+use crate::{data::RonAssetApp, netplay::systems::netplay_active};
+
+use self::{
+    resources::{ParticleEffectSet, ParticlePool},
+    systems::{load_particle_effects, trigger_effects, emit_particles, update_particles},
+};
+
+// cosmetic particles for hits, pickups, ufos leaving the playfield, level ups and game overs,
+// defined in `assets/data/default.particles.ron`. they are not part of the rollback,
+// and online matches go without them as a rollback would set the same effects off twice
+pub struct ParticlePlug;
+
+impl Plugin for ParticlePlug {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<ParticleEffectSet>()
+            .init_resource::<ParticlePool>()
+            .add_systems(Startup, load_particle_effects)
+            .add_systems(PostUpdate, trigger_effects.run_if(not(netplay_active)))
+            .add_systems(Update, (emit_particles, update_particles).chain());
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::{prelude::*, reflect::TypePath, utils::HashMap};
+use serde::Deserialize;
+
+// what sets an effect off
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EffectKind {
+    // an enemy ran into by a character or a projectile
+    Hit,
+    Pickup,
+    // an ufo leaving the playfield
+    Despawn,
+    LevelUp,
+    GameOver,
+}
+
+// how the particles of an effect are emitted and how they look over their lifetime.
+// ranges are (min, max), a particle draws its own value in them
+#[derive(Deserialize)]
+pub struct ParticleEffect {
+    // particles emitted at once
+    pub count: u32,
+    // particles emitted every second after that, for `duration` seconds
+    #[serde(default)]
+    pub rate: f32,
+    #[serde(default)]
+    pub duration: f32,
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+    // in degrees, 90 is up, the particles go within `spread` degrees around it
+    pub direction: f32,
+    pub spread: f32,
+    #[serde(default)]
+    pub gravity: f32,
+    // size at the start and at the end of the lifetime
+    pub size: (f32, f32),
+    pub start_color: (f32, f32, f32, f32),
+    pub end_color: (f32, f32, f32, f32),
+}
+
+#[derive(Asset, TypePath, Deserialize)]
+pub struct ParticleEffectSet {
+    // particles alive at the same time over every effect, the others are not emitted
+    pub budget: usize,
+    pub effects: HashMap<EffectKind, ParticleEffect>,
+}
+
+#[derive(Resource)]
+pub struct ParticleEffectSetHandle {
+    pub handle: Handle<ParticleEffectSet>,
+}
+
+// particles are spawned once and reused, the free ones are hidden
+#[derive(Resource, Default)]
+pub struct ParticlePool {
+    pub free: Vec<Entity>,
+    pub spawned: usize,
+}
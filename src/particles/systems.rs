@@ -0,0 +1,156 @@
+use crate::{
+    background::{events::LevelUpEvent, resources::GameBonudary},
+    objects::{components::UfoType, events::UfoDespawnEvent},
+    player::components::Character,
+    systems::GameOver,
+};
+
+use super::{
+    components::{Emitter, Particle},
+    resources::{EffectKind, ParticleEffectSet, ParticleEffectSetHandle, ParticlePool},
+};
+
+use bevy::prelude::*;
+
+// over the playfield, under the overlay
+const PARTICLE_Z: f32 = 4.;
+
+pub fn load_particle_effects(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let handle: Handle<ParticleEffectSet> = asset_server.load("data/default.particles.ron");
+    commands.insert_resource(ParticleEffectSetHandle { handle });
+}
+
+fn spawn_emitter(commands: &mut Commands, kind: EffectKind, translation: Vec3) {
+    commands.spawn((
+        Emitter {
+            kind,
+            age: 0.,
+            emitted: 0,
+        },
+        Transform::from_translation(translation),
+    ));
+}
+
+// ufos burst where they were despawned, level ups and game overs from the characters,
+// or from the middle of the playfield once they are all gone
+pub fn trigger_effects(
+    mut commands: Commands,
+    mut ufo_despawn_events: EventReader<UfoDespawnEvent>,
+    mut level_up_events: EventReader<LevelUpEvent>,
+    mut game_over_events: EventReader<GameOver>,
+    character_query: Query<&Transform, With<Character>>,
+    game_boundary: Res<GameBonudary>,
+) {
+    for event in ufo_despawn_events.read() {
+        let kind = match (event.collided, event.kind) {
+            (false, _) => EffectKind::Despawn,
+            (true, UfoType::Enemy) => EffectKind::Hit,
+            (true, UfoType::Bonus) => EffectKind::Pickup,
+        };
+        spawn_emitter(&mut commands, kind, event.translation);
+    }
+
+    let mut origins: Vec<Vec3> = character_query.iter().map(|transform| transform.translation).collect();
+    if origins.is_empty() {
+        origins.push(Vec3::new(game_boundary.x_mid, game_boundary.y_mid, 0.));
+    }
+    for _ in level_up_events.read() {
+        for origin in origins.iter() {
+            spawn_emitter(&mut commands, EffectKind::LevelUp, *origin);
+        }
+    }
+    for _ in game_over_events.read() {
+        for origin in origins.iter() {
+            spawn_emitter(&mut commands, EffectKind::GameOver, *origin);
+        }
+    }
+}
+
+// a free particle is reused before a new one is spawned, and none past the budget.
+// the particles draw from their own rng, the one of the gameplay has to stay the same for replays
+pub fn emit_particles(
+    mut commands: Commands,
+    mut emitter_query: Query<(Entity, &mut Emitter, &Transform)>,
+    mut particle_query: Query<(&mut Particle, &mut Transform, &mut Sprite, &mut Visibility), Without<Emitter>>,
+    mut particle_pool: ResMut<ParticlePool>,
+    effect_sets: Res<Assets<ParticleEffectSet>>,
+    effect_set_handle: Res<ParticleEffectSetHandle>,
+    time: Res<Time>,
+) {
+    let effect_set = effect_sets.get(&effect_set_handle.handle);
+    let mut rng = rand::thread_rng();
+
+    for (entity, mut emitter, transform) in emitter_query.iter_mut() {
+        // nothing to show without the effect definitions
+        let Some((effect, budget)) = effect_set.and_then(|effect_set| {
+            effect_set.effects.get(&emitter.kind).map(|effect| (effect, effect_set.budget))
+        }) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        emitter.age += time.delta_seconds();
+        let due = effect.count + (effect.rate * emitter.age.min(effect.duration)) as u32;
+        let translation = transform.translation.truncate().extend(PARTICLE_Z);
+        for _ in emitter.emitted..due {
+            let particle = Particle::new(effect, &mut rng);
+            let sprite = Sprite {
+                color: particle.color(),
+                custom_size: Some(Vec2::splat(particle.size())),
+                ..default()
+            };
+
+            if let Some(free) = particle_pool.free.pop() {
+                let Ok((mut slot, mut slot_transform, mut slot_sprite, mut visibility)) = particle_query.get_mut(free) else {
+                    continue;
+                };
+                *slot = particle;
+                slot_transform.translation = translation;
+                *slot_sprite = sprite;
+                *visibility = Visibility::Inherited;
+            } else if particle_pool.spawned < budget {
+                particle_pool.spawned += 1;
+                commands.spawn((
+                    particle,
+                    SpriteBundle {
+                        transform: Transform::from_translation(translation),
+                        sprite,
+                        ..default()
+                    },
+                ));
+            } else {
+                break;
+            }
+        }
+        emitter.emitted = due;
+
+        if emitter.age >= effect.duration {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn update_particles(
+    mut particle_query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite, &mut Visibility)>,
+    mut particle_pool: ResMut<ParticlePool>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut particle, mut transform, mut sprite, mut visibility) in particle_query.iter_mut() {
+        if !particle.alive {
+            continue;
+        }
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            particle.alive = false;
+            *visibility = Visibility::Hidden;
+            particle_pool.free.push(entity);
+            continue;
+        }
+
+        particle.velocity.y += particle.gravity * delta;
+        transform.translation += (particle.velocity * delta).extend(0.);
+        sprite.color = particle.color();
+        sprite.custom_size = Some(Vec2::splat(particle.size()));
+    }
+}
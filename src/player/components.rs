@@ -1,30 +1,84 @@
 use bevy::prelude::*;
 
-use crate::objects::components::CollisionObject;
+use crate::objects::{components::{CollisionObject, HitboxSource}, hitbox::HitboxKind};
 
-use super::systems::{CHARACTER_WIDTH, CHARACTER_HEIGHT};
+use super::systems::{
+    CHARACTER_WIDTH, CHARACTER_HEIGHT, DASH_DURATION, DASH_COOLDOWN, DASH_INVULNERABLE_DURATION
+};
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct Character {
+    // index of the player controlling it
+    pub player: usize,
     pub is_head: bool,
     pub last_flip: f32,
     pub size: Vec3,
     pub invulnerable_until: f32,
+    // last direction the character moved to, used when dashing while standing still
+    pub facing: Vec3,
+    pub dash_direction: Vec3,
+    pub dash_until: f32,
+    pub dash_ready_at: f32,
 }
 
 impl Default for Character {
     fn default() -> Self {
         Character {
+            player: 0,
             is_head: true,
             last_flip: 0.,
             size: Vec3::new(CHARACTER_WIDTH, CHARACTER_HEIGHT, 1.),
             invulnerable_until: 0.,
+            facing: Vec3::new(0., 1., 0.),
+            dash_direction: Vec3::ZERO,
+            dash_until: 0.,
+            dash_ready_at: 0.,
         }
     }
 }
 
+impl Character {
+    pub fn new(player: usize, hitbox_scale: f32) -> Self {
+        Character {
+            player,
+            size: Vec3::new(CHARACTER_WIDTH * hitbox_scale, CHARACTER_HEIGHT * hitbox_scale, 1.),
+            ..default()
+        }
+    }
+
+    // returns true if the hit costs a life, i.e. the character was not invulnerable
+    pub fn hit(&mut self, now: f32, invulnerable_duration: f32) -> bool {
+        if self.invulnerable_until < now {
+            self.invulnerable_until = now + invulnerable_duration;
+            return true;
+        }
+        false
+    }
+
+    pub fn is_dashing(&self, now: f32) -> bool {
+        self.dash_until > now
+    }
+
+    pub fn dash_cooldown(&self, now: f32) -> f32 {
+        (self.dash_ready_at - now).max(0.)
+    }
+
+    pub fn dash(&mut self, direction: Vec3, now: f32) {
+        self.dash_direction = direction;
+        self.dash_until = now + DASH_DURATION;
+        self.dash_ready_at = now + DASH_COOLDOWN;
+        self.invulnerable_until = self.invulnerable_until.max(now + DASH_INVULNERABLE_DURATION);
+    }
+}
+
 impl CollisionObject for Character {
     fn get_dimension(&self) -> (f32, f32) {
         (self.size.x, self.size.y)
     }
+}
+
+impl HitboxSource for Character {
+    fn hitbox_kind(&self) -> HitboxKind {
+        HitboxKind::Character
+    }
 }
\ No newline at end of file
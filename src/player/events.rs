@@ -1,9 +1,21 @@
 use bevy::prelude::*;
 
 #[derive(Event)]
-pub struct LoseLifeEvent {}
+pub struct LoseLifeEvent {
+    pub player: usize,
+}
+
+// what the points were earned with
+#[derive(Clone, Copy, PartialEq)]
+pub enum PointSource {
+    Bonus,
+    Enemy,
+    Boss,
+}
 
 #[derive(Event)]
 pub struct EarnPointEvent {
+    pub player: usize,
     pub scores: u32,
+    pub source: PointSource,
 }
\ No newline at end of file
@@ -3,19 +3,25 @@ pub mod resources;
 pub mod systems;
 pub mod events;
 
-use bevy::{prelude::{Plugin, App}, app::{Startup, Update}};
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, OnEnter, resource_changed, Condition}, app::{Startup, FixedUpdate, Update}};
 
-use self::{systems::{spawn_character, character_movement, init_character_texture, obj_collision}, events::{LoseLifeEvent, EarnPointEvent}};
+use crate::{collision::CollisionSet, skins::resources::{SkinChoice, Skins}, objects::systems::resolve_hitboxes, simulation::{SimulationSet, rollback::RollbackApp}, systems::AppState};
+
+use self::{components::Character, resources::MatchSettings, systems::{spawn_characters, character_movement, init_character_texture, take_enemy_damage, pick_up_bonus}, events::{LoseLifeEvent, EarnPointEvent}};
 
 pub struct CharacterPlug;
 
 impl Plugin for CharacterPlug {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_character)
+        app.init_resource::<MatchSettings>()
+            .rollback_component::<Character>()
+            .add_systems(OnEnter(AppState::Playing), spawn_characters)
             .add_systems(Startup, init_character_texture)
+            .add_systems(Update, init_character_texture.run_if(resource_changed::<SkinChoice>().or_else(resource_changed::<Skins>())))
             .add_event::<LoseLifeEvent>()
             .add_event::<EarnPointEvent>()
-            .add_systems(Update, character_movement)
-            .add_systems(Update, obj_collision);
+            .add_systems(FixedUpdate, resolve_hitboxes::<Character>.in_set(SimulationSet::Begin))
+            .add_systems(FixedUpdate, character_movement.in_set(CollisionSet::Movement))
+            .add_systems(FixedUpdate, (take_enemy_damage, pick_up_bonus).in_set(CollisionSet::Reaction));
     }
 }
\ No newline at end of file
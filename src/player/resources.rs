@@ -1,7 +1,66 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::{modes::resources::GameMode, mutators::resources::Mutators};
+
+// sprites of the skin worn by the characters
 #[derive(Resource)]
 pub struct CharacterTexture {
     pub head: Handle<Image>,
-    pub tail: Handle<Image>
-}
\ No newline at end of file
+    pub tail: Handle<Image>,
+}
+
+// who loses when a player runs out of lifes
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum GameOverRule {
+    // the run ends as soon as any player is out
+    #[default]
+    Shared,
+    // a player out of lifes leaves the playfield, the run goes on until every player is out
+    Separate,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum MatchMode {
+    // every player runs on the playfield at once
+    #[default]
+    CoOp,
+    // one player runs while the other drops the ufo, then they swap
+    Versus,
+}
+
+// how the match is set up in the menu
+#[derive(Resource, Clone, Copy, Serialize, Deserialize)]
+pub struct MatchSettings {
+    pub players: usize,
+    pub game_over: GameOverRule,
+    #[serde(default)]
+    pub mode: MatchMode,
+    #[serde(default)]
+    pub game_mode: GameMode,
+    #[serde(default)]
+    pub mutators: Mutators,
+}
+
+impl Default for MatchSettings {
+    fn default() -> Self {
+        MatchSettings {
+            players: 1,
+            game_over: GameOverRule::Shared,
+            mode: MatchMode::CoOp,
+            game_mode: GameMode::Endless,
+            mutators: Mutators::default(),
+        }
+    }
+}
+
+impl MatchSettings {
+    // runs set up this way score the same, so they can be compared
+    pub fn same_rules(&self, other: &MatchSettings) -> bool {
+        self.players == other.players
+            && self.game_over == other.game_over
+            && self.mode == other.mode
+            && self.game_mode == other.game_mode
+            && self.mutators == other.mutators
+    }
+}
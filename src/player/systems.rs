@@ -1,58 +1,99 @@
 use crate::{
-    background::resources::GameBonudary, 
-    objects::components::{
-        UFO, CollisionObject, UfoType
-    }
+    background::resources::{GameBonudary, RunRules},
+    collision::{components::CollisionProbe, events::CollisionEvent},
+    objects::components::{Ufo, UfoType},
+    simulation::{resources::{TickInput, MAX_PLAYERS}, rollback::Rollback},
+    skins::resources::{SkinChoice, Skins},
+};
+use super::{
+    components::Character,
+    resources::{CharacterTexture, MatchSettings, MatchMode},
+    events::{LoseLifeEvent, EarnPointEvent, PointSource},
 };
-use super::{components::Character, resources::CharacterTexture, events::{LoseLifeEvent, EarnPointEvent}};
 
 use bevy::{prelude::*, window::PrimaryWindow};
 
 pub const CHARACTER_HEIGHT: f32 = 84.;
 pub const CHARACTER_WIDTH: f32 = 70.;
-const CHARACTER_MOVEMENT_SPEED: f32 = 300.0;
+pub const CHARACTER_MOVEMENT_SPEED: f32 = 300.0;
 const CHARACTER_TEXTURE_FLIP_FREQ: f32 = 0.25;
-const INVULNERABLE_DURATION: f32 = 3.;
-
+pub const INVULNERABLE_DURATION: f32 = 3.;
+pub const DASH_DURATION: f32 = 0.15;
+pub const DASH_COOLDOWN: f32 = 2.;
+pub const DASH_INVULNERABLE_DURATION: f32 = 0.3;
+const DASH_SPEED_MULTIPLIER: f32 = 4.;
+// the first player keeps the plain yellow alien
+pub const PLAYER_TINTS: [Color; MAX_PLAYERS] = [Color::WHITE, Color::rgb(0.55, 0.8, 1.)];
+
+// follows the skin picked in the menu
 pub fn init_character_texture(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    skins: Res<Skins>,
+    skin_choice: Res<SkinChoice>,
 ) {
-    let head: Handle<Image> = asset_server.load("sprites/characters/alienYellow_walk2.png");
-    let tail: Handle<Image> = asset_server.load("sprites/characters/alienYellow_walk1.png");
+    let skin = skins.worn(&skin_choice);
+    let head: Handle<Image> = asset_server.load(&skin.head);
+    let tail: Handle<Image> = asset_server.load(&skin.tail);
 
     commands.insert_resource(CharacterTexture { head, tail });
 }
 
 pub fn spawn_character(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    media_server: Res<AssetServer>,
+    commands: &mut Commands,
+    character_texture: &CharacterTexture,
+    translation: Vec3,
+    player: usize,
+    hitbox_scale: f32,
 ) {
-    let window = window_query.get_single().unwrap();
-
-    // random spawn character on the lower 1/3 of the window
-    let x = window.width() / 2.;
-    let y = CHARACTER_HEIGHT * 1.5;
-
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(x, y, 0.),
-            texture: media_server.load("sprites/characters/alienYellow_walk2.png"),
+            transform: Transform::from_translation(translation),
+            texture: character_texture.head.clone(),
+            sprite: Sprite {
+                color: PLAYER_TINTS[player],
+                ..default()
+            },
             ..default()
         },
-        Character { ..default() }
+        Character::new(player, hitbox_scale),
+        CollisionProbe,
+        Rollback,
     ));
 }
 
+pub fn spawn_characters(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    character_texture: Res<CharacterTexture>,
+    match_settings: Res<MatchSettings>,
+    run_rules: Res<RunRules>,
+) {
+    let window = window_query.get_single().unwrap();
+    // in versus only the first runner is on the playfield
+    let players = match match_settings.mode {
+        MatchMode::CoOp => match_settings.players.clamp(1, MAX_PLAYERS),
+        MatchMode::Versus => 1,
+    };
+
+    // spawn the characters side by side in the middle, near the bottom of the window
+    let y = CHARACTER_HEIGHT * 1.5;
+    for player in 0..players {
+        let x = window.width() / 2. + (player as f32 - (players - 1) as f32 / 2.) * CHARACTER_WIDTH * 2.;
+        spawn_character(&mut commands, &character_texture, Vec3::new(x, y, 0.), player, run_rules.character_hitbox_scale);
+    }
+}
+
 // given an new xyz and the window frame, return an possible xyz
 fn confine_movement(translation: &Vec3, boundary: &GameBonudary) -> Vec3 {
-    let mut output = translation.clone();
+    let mut output = *translation;
     if translation.x > boundary.x_max {
         output.x = boundary.x_max;
     } else if translation.x < boundary.x_min {
         output.x = boundary.x_min;
-    } else if translation.y > boundary.y_max {
+    }
+
+    if translation.y > boundary.y_max {
         output.y = boundary.y_max;
     } else if translation.y < boundary.y_min {
         output.y = boundary.y_min
@@ -61,89 +102,90 @@ fn confine_movement(translation: &Vec3, boundary: &GameBonudary) -> Vec3 {
     output
 }
 
-// handle the movement of character in response to keyboard action
+// handle the movement of every character in response to the actions of its player during this tick
 pub fn character_movement(
     mut character_query: Query<(&mut Transform, &mut Character, &mut Handle<Image>)>,
-    keyboard_input: Res<Input<KeyCode>>,
+    tick_input: Res<TickInput>,
     time: Res<Time>,
     character_texture: Res<CharacterTexture>,
     game_boundary: Res<GameBonudary>,
 ) {
-    if let Ok((mut transform, mut character, mut texture)) = character_query.get_single_mut() {
-        let mut movement_direction = Vec3::ZERO;
-
-        if keyboard_input.pressed(KeyCode::Up) {
-            movement_direction += Vec3::new(0., 1., 0.);
-        }
-
-        if keyboard_input.pressed(KeyCode::Down) {
-            movement_direction += Vec3::new(0., -1., 0.);
-        }
-
-        if keyboard_input.pressed(KeyCode::Left) {
-            movement_direction += Vec3::new(-1., 0., 0.);
-        }
-
-        if keyboard_input.pressed(KeyCode::Right) {
-            movement_direction += Vec3::new(1., 1., 0.);
-        }
+    for (mut transform, mut character, mut texture) in character_query.iter_mut() {
+        let player_input = tick_input.players[character.player];
+        let mut movement_direction = player_input.movement.extend(0.);
 
+        let now = time.elapsed_seconds();
         if movement_direction.length() > 0. {
             movement_direction = movement_direction.normalize();
-            let elapsed = time.elapsed_seconds();
-            if elapsed - character.last_flip > CHARACTER_TEXTURE_FLIP_FREQ {
+            character.facing = movement_direction;
+            if now - character.last_flip > CHARACTER_TEXTURE_FLIP_FREQ {
                 if character.is_head {
                     *texture = character_texture.tail.clone();
                 } else {
                     *texture = character_texture.head.clone();
                 }
                 character.is_head = !character.is_head;
-                character.last_flip = elapsed;
+                character.last_flip = now;
             }
         }
 
-        let new_translation = transform.translation + movement_direction * CHARACTER_MOVEMENT_SPEED * time.delta_seconds();
+        // dash in the current direction, or the last one when standing still
+        if player_input.dash && character.dash_cooldown(now) <= 0. {
+            let direction = character.facing;
+            character.dash(direction, now);
+        }
+
+        let mut speed = CHARACTER_MOVEMENT_SPEED;
+        if character.is_dashing(now) {
+            movement_direction = character.dash_direction;
+            speed *= DASH_SPEED_MULTIPLIER;
+        }
+
+        let new_translation = transform.translation + movement_direction * speed * time.delta_seconds();
         transform.translation = confine_movement(&new_translation, game_boundary.as_ref());
     }
 }
 
-// define the behavior when user collide with an ufo
-pub fn obj_collision(
-    mut commands: Commands,
+// running into an enemy costs a life, unless the character is invulnerable
+pub fn take_enemy_damage(
+    mut collision_events: EventReader<CollisionEvent>,
     mut lose_life_event: EventWriter<LoseLifeEvent>,
-    mut earn_point_event: EventWriter<EarnPointEvent>,
-    mut character_query: Query<(&Transform, &mut Character)>,
-    ufo_query: Query<(Entity, &Transform, &UFO), With<UFO>>,
+    mut character_query: Query<&mut Character>,
+    ufo_query: Query<&Ufo>,
+    run_rules: Res<RunRules>,
     time: Res<Time>,
 ) {
-    if let Ok((char_transform, mut character)) = character_query.get_single_mut() {
-        for (ufo_entity, ufo_transform, ufo ) in ufo_query.iter() {
-            let is_collide = character.collide(
-                char_transform.translation, 
-                ufo, 
-                ufo_transform.translation
-            );
-            if is_collide {
-                match ufo.kind {
-                    UfoType::ENEMY => {
-                        let now = time.elapsed_seconds();
-                        if character.invulnerable_until < now {
-                            character.invulnerable_until = time.elapsed_seconds() + INVULNERABLE_DURATION;
-                            lose_life_event.send(LoseLifeEvent {});
-                        }
-                    },
-                    UfoType::BONUS => {
-                        // update scorse
-                        println!("send events");
-                        earn_point_event.send(EarnPointEvent {
-                            scores: ufo.score,
-                        })
-                    }
-                }
-
-                // despawn the ufo no matter what
-                commands.entity(ufo_entity).despawn();
+    for event in collision_events.read() {
+        let (Ok(mut character), Ok(ufo)) = (character_query.get_mut(event.a), ufo_query.get(event.b)) else {
+            continue;
+        };
+        if let UfoType::Enemy = ufo.kind {
+            if character.hit(time.elapsed_seconds(), run_rules.invulnerable_duration) {
+                lose_life_event.send(LoseLifeEvent {
+                    player: character.player,
+                });
             }
         }
     }
-}
\ No newline at end of file
+}
+
+pub fn pick_up_bonus(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut earn_point_event: EventWriter<EarnPointEvent>,
+    character_query: Query<&Character>,
+    ufo_query: Query<&Ufo>,
+    run_rules: Res<RunRules>,
+) {
+    for event in collision_events.read() {
+        let (Ok(character), Ok(ufo)) = (character_query.get(event.a), ufo_query.get(event.b)) else {
+            continue;
+        };
+        if let UfoType::Bonus = ufo.kind {
+            earn_point_event.send(EarnPointEvent {
+                player: character.player,
+                scores: ufo.score * run_rules.bonus_score,
+                source: PointSource::Bonus,
+            });
+        }
+    }
+}
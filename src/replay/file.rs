@@ -0,0 +1,292 @@
+use std::{fmt, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    challenge::resources::DailyChallenge,
+    player::resources::MatchSettings,
+    simulation::resources::{PlayerInput, TickInput, TOGGLE_SHOOTER},
+};
+
+// every player has a byte of buttons, the first player in the lowest one,
+// the shooter toggle sits in the byte of the first player
+const PLAYER_BITS: usize = 8;
+
+pub const REPLAY_DIR: &str = "replays";
+const REPLAY_EXTENSION: &str = ".replay.ron";
+const TRACE_INTERVAL: u32 = 6;
+
+// consecutive ticks sharing the same buttons, as (ticks, buttons)
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct InputRun(pub u32, pub u16);
+
+fn encode_input(input: &TickInput) -> u16 {
+    let mut buttons = 0;
+    for (player, player_input) in input.players.iter().enumerate() {
+        buttons |= (player_input.to_bits() as u16) << (player * PLAYER_BITS);
+    }
+    if input.toggle_shooter {
+        buttons |= TOGGLE_SHOOTER as u16;
+    }
+    buttons
+}
+
+fn decode_input(buttons: u16) -> TickInput {
+    let mut input = TickInput {
+        toggle_shooter: buttons & TOGGLE_SHOOTER as u16 != 0,
+        ..Default::default()
+    };
+    for (player, player_input) in input.players.iter_mut().enumerate() {
+        *player_input = PlayerInput::from_bits((buttons >> (player * PLAYER_BITS)) as u8);
+    }
+    input
+}
+
+// where the character was and the score it had, as (x, y, score)
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct TracePoint(pub f32, pub f32, pub u32);
+
+// everything needed to play a run again: the simulation settings and the input of every tick
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub tick_rate: f64,
+    #[serde(default)]
+    pub match_settings: MatchSettings,
+    // the daily challenge played, with its modifiers
+    #[serde(default)]
+    pub challenge: Option<DailyChallenge>,
+    pub ticks: u32,
+    // score at the end of the last tick, checked on playback
+    pub score: u32,
+    pub inputs: Vec<InputRun>,
+    // a point at the end of every `trace_interval` ticks, to show the run without simulating it
+    #[serde(default)]
+    pub trace_interval: u32,
+    #[serde(default)]
+    pub trace: Vec<TracePoint>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, tick_rate: f64) -> Self {
+        Replay {
+            seed,
+            tick_rate,
+            match_settings: MatchSettings::default(),
+            challenge: None,
+            ticks: 0,
+            score: 0,
+            inputs: Vec::new(),
+            trace_interval: TRACE_INTERVAL,
+            trace: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, input: &TickInput) {
+        let buttons = encode_input(input);
+        match self.inputs.last_mut() {
+            Some(InputRun(ticks, last)) if *last == buttons => *ticks += 1,
+            _ => self.inputs.push(InputRun(1, buttons)),
+        }
+        self.ticks += 1;
+    }
+
+    // forget every tick from `ticks` on, e.g. when they are played again after a rollback
+    pub fn truncate(&mut self, ticks: u32) {
+        while self.ticks > ticks {
+            let Some(InputRun(run, _)) = self.inputs.last_mut() else {
+                break;
+            };
+            let removed = (*run).min(self.ticks - ticks);
+            *run -= removed;
+            self.ticks -= removed;
+            if *run == 0 {
+                self.inputs.pop();
+            }
+        }
+        if let Some(points) = self.ticks.checked_div(self.trace_interval) {
+            self.trace.truncate(points as usize);
+        }
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.ticks as f32 / self.tick_rate as f32
+    }
+
+    // the trace at `seconds` into the run, in between the two closest points
+    pub fn trace_at(&self, seconds: f32) -> Option<TracePoint> {
+        if self.trace_interval == 0 {
+            return None;
+        }
+        let last = self.trace.len().checked_sub(1)?;
+        let position = (seconds * self.tick_rate as f32 / self.trace_interval as f32 - 1.).max(0.);
+        let index = (position as usize).min(last);
+        let (TracePoint(x, y, score), TracePoint(next_x, next_y, _)) = (self.trace[index], self.trace[(index + 1).min(last)]);
+        let progress = (position - index as f32).min(1.);
+        Some(TracePoint(x + (next_x - x) * progress, y + (next_y - y) * progress, score))
+    }
+
+    // input of the tick at the `cursor`, which is (run, tick within the run), moving the cursor forward
+    pub fn next_input(&self, cursor: &mut (usize, u32)) -> Option<TickInput> {
+        let InputRun(ticks, buttons) = *self.inputs.get(cursor.0)?;
+        cursor.1 += 1;
+        if cursor.1 >= ticks {
+            *cursor = (cursor.0 + 1, 0);
+        }
+        Some(decode_input(buttons))
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    Ron(ron::Error),
+    Parse(ron::error::SpannedError),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "could not access replay: {err}"),
+            ReplayError::Ron(err) => write!(f, "could not write replay: {err}"),
+            ReplayError::Parse(err) => write!(f, "could not parse replay: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<std::io::Error> for ReplayError {
+    fn from(err: std::io::Error) -> Self {
+        ReplayError::Io(err)
+    }
+}
+
+impl From<ron::Error> for ReplayError {
+    fn from(err: ron::Error) -> Self {
+        ReplayError::Ron(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for ReplayError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        ReplayError::Parse(err)
+    }
+}
+
+pub fn load_replay(path: impl AsRef<Path>) -> Result<Replay, ReplayError> {
+    Ok(ron::from_str(&fs::read_to_string(path)?)?)
+}
+
+pub fn replay_path(name: impl fmt::Display) -> String {
+    format!("{REPLAY_DIR}/{name}{REPLAY_EXTENSION}")
+}
+
+// highest scoring run among the recorded ones that have a trace
+// only runs played by the same rules, and on the same daily challenge, are worth racing
+pub fn best_replay(match_settings: &MatchSettings, challenge_date: Option<&str>) -> Option<Replay> {
+    fs::read_dir(REPLAY_DIR)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(REPLAY_EXTENSION))
+        .filter_map(|entry| load_replay(entry.path()).ok())
+        .filter(|replay| !replay.trace.is_empty())
+        .filter(|replay| replay.match_settings.same_rules(match_settings))
+        .filter(|replay| replay.challenge.as_ref().map(|challenge| challenge.date.as_str()) == challenge_date)
+        .max_by_key(|replay| replay.score)
+}
+
+pub fn save_replay(replay: &Replay, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+    if let Some(dir) = path.as_ref().parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, ron::to_string(replay)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Vec2;
+
+    use super::*;
+
+    fn input(x: f32, fire: bool) -> TickInput {
+        let mut input = TickInput::default();
+        input.players[0] = PlayerInput { movement: Vec2::new(x, 0.), fire, ..Default::default() };
+        input
+    }
+
+    fn recorded(inputs: &[TickInput]) -> Replay {
+        let mut replay = Replay::new(0, 60.);
+        for input in inputs {
+            replay.push(input);
+        }
+        replay
+    }
+
+    fn played(replay: &Replay) -> Vec<TickInput> {
+        let mut cursor = (0, 0);
+        std::iter::from_fn(|| replay.next_input(&mut cursor)).collect()
+    }
+
+    #[test]
+    fn buttons_survive_encoding() {
+        let mut both = input(-1., true);
+        both.players[1] = PlayerInput { movement: Vec2::new(1., 1.), dash: true, ..Default::default() };
+        both.toggle_shooter = true;
+        assert!(decode_input(encode_input(&both)) == both);
+        assert!(decode_input(encode_input(&TickInput::default())) == TickInput::default());
+    }
+
+    #[test]
+    fn held_buttons_share_a_run() {
+        let replay = recorded(&[input(1., false), input(1., false), input(0., true), input(1., false)]);
+        let runs: Vec<u32> = replay.inputs.iter().map(|InputRun(ticks, _)| *ticks).collect();
+        assert_eq!(runs, vec![2, 1, 1]);
+        assert_eq!(replay.ticks, 4);
+    }
+
+    #[test]
+    fn playback_gives_back_every_tick() {
+        let inputs = [input(1., false), input(1., false), input(0., true), input(-1., false)];
+        let replay = recorded(&inputs);
+        assert!(played(&replay) == inputs);
+    }
+
+    #[test]
+    fn truncate_shortens_the_last_run() {
+        let inputs = [input(1., false), input(0., true), input(0., true), input(0., true)];
+        let mut replay = recorded(&inputs);
+        replay.truncate(2);
+        assert_eq!(replay.ticks, 2);
+        assert!(played(&replay) == inputs[..2]);
+    }
+
+    #[test]
+    fn truncate_drops_emptied_runs() {
+        let inputs = [input(1., false), input(1., false), input(0., true), input(-1., false)];
+        let mut replay = recorded(&inputs);
+        replay.truncate(2);
+        assert_eq!(replay.inputs.len(), 1);
+        assert!(played(&replay) == inputs[..2]);
+
+        replay.truncate(0);
+        assert!(replay.inputs.is_empty());
+    }
+
+    #[test]
+    fn truncate_past_the_end_keeps_everything() {
+        let mut replay = recorded(&[input(1., false), input(0., true)]);
+        replay.truncate(10);
+        assert_eq!(replay.ticks, 2);
+        assert_eq!(replay.inputs.len(), 2);
+    }
+
+    #[test]
+    fn truncate_drops_the_trace_of_forgotten_ticks() {
+        let mut replay = recorded(&vec![input(1., false); 4 * TRACE_INTERVAL as usize]);
+        replay.trace = (0..4).map(|point| TracePoint(0., 0., point)).collect();
+        replay.truncate(2 * TRACE_INTERVAL + 1);
+        assert_eq!(replay.trace.len(), 2);
+    }
+}
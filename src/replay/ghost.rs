@@ -0,0 +1,168 @@
+use crate::{
+    background::resources::GameMetadata,
+    challenge::resources::DailyChallenge,
+    hud::{components::HudSlot, HudSet},
+    player::resources::{CharacterTexture, MatchSettings},
+    systems::{cli_flag, AppState},
+};
+
+use super::file::{best_replay, Replay};
+
+use bevy::{
+    prelude::*,
+    tasks::{block_on, IoTaskPool, Task},
+};
+
+const GHOST_ALPHA: f32 = 0.4;
+
+// race against the best recorded run: its character is drawn see-through next to the live one,
+// and the hud tells how far the live score is ahead or behind. run with `--ghost`
+pub struct GhostPlug;
+
+#[derive(Resource)]
+pub struct GhostRun {
+    pub replay: Replay,
+}
+
+// the replays are read off the disk in the background, once the rules of the run are known
+#[derive(Resource)]
+pub struct GhostLoading(Task<Option<Replay>>);
+
+#[derive(Component)]
+pub struct Ghost;
+
+#[derive(Component)]
+pub struct GhostDelta;
+
+impl Plugin for GhostPlug {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_ghost)
+            .add_systems(OnEnter(AppState::Playing), (load_ghost_run, spawn_ghost_delta.after(HudSet::Spawn)))
+            .add_systems(Update, (
+                receive_ghost_run.run_if(resource_exists::<GhostLoading>()),
+                (ghost_movement, update_ghost_delta).run_if(resource_exists::<GhostRun>()),
+                dress_ghost.run_if(resource_exists::<CharacterTexture>().and_then(resource_changed::<CharacterTexture>())),
+            ));
+    }
+}
+
+pub fn ghost_enabled() -> bool {
+    cli_flag("--ghost")
+}
+
+// the ghost follows the run time of the simulation, so it starts and pauses along with it
+fn ghost_seconds(fixed_time: &Time<Fixed>) -> f32 {
+    fixed_time.elapsed_seconds() + fixed_time.overstep().as_secs_f32()
+}
+
+fn load_ghost_run(mut commands: Commands, match_settings: Res<MatchSettings>, challenge: Option<Res<DailyChallenge>>) {
+    let match_settings = *match_settings;
+    let challenge_date = challenge.map(|challenge| challenge.date.clone());
+    let task = IoTaskPool::get().spawn(async move { best_replay(&match_settings, challenge_date.as_deref()) });
+    commands.remove_resource::<GhostRun>();
+    commands.insert_resource(GhostLoading(task));
+}
+
+fn receive_ghost_run(mut commands: Commands, mut ghost_loading: ResMut<GhostLoading>) {
+    if !ghost_loading.0.is_finished() {
+        return;
+    }
+    commands.remove_resource::<GhostLoading>();
+    match block_on(&mut ghost_loading.0) {
+        Some(replay) => {
+            info!("racing a ghost with score {}", replay.score);
+            commands.insert_resource(GhostRun { replay });
+        },
+        None => info!("no recorded run with these rules to race against yet"),
+    }
+}
+
+fn spawn_ghost(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(0., 0., -0.5),
+            sprite: Sprite {
+                color: Color::WHITE.with_a(GHOST_ALPHA),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        Ghost,
+    ));
+}
+
+fn spawn_ghost_delta(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    slot_query: Query<(Entity, &HudSlot)>,
+) {
+    let Some((slot, _)) = slot_query.iter().find(|(_, slot)| **slot == HudSlot::TopRight) else {
+        return;
+    };
+
+    let text_ghost = commands.spawn((
+        GhostDelta,
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 20.,
+                    color: Color::WHITE,
+                    font: asset_server.load("BungeeSpice-Regular.ttf"),
+                },
+            ).with_alignment(TextAlignment::Right),
+            ..default()
+        },
+    )).id();
+    commands.entity(slot).add_child(text_ghost);
+}
+
+// the ghost wears the skin of the characters, which may still change once the skin file is loaded or in the menu
+fn dress_ghost(character_texture: Res<CharacterTexture>, mut ghost_query: Query<&mut Handle<Image>, With<Ghost>>) {
+    for mut texture in ghost_query.iter_mut() {
+        *texture = character_texture.head.clone();
+    }
+}
+
+// the ghost disappears once its run is over
+fn ghost_movement(
+    ghost_run: Res<GhostRun>,
+    fixed_time: Res<Time<Fixed>>,
+    mut ghost_query: Query<(&mut Transform, &mut Visibility), With<Ghost>>,
+) {
+    let seconds = ghost_seconds(&fixed_time);
+    let trace = ghost_run.replay.trace_at(seconds).filter(|_| seconds <= ghost_run.replay.duration());
+    for (mut transform, mut visibility) in ghost_query.iter_mut() {
+        match trace {
+            Some(trace) => {
+                transform.translation.x = trace.0;
+                transform.translation.y = trace.1;
+                *visibility = Visibility::Visible;
+            },
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn update_ghost_delta(
+    ghost_run: Res<GhostRun>,
+    fixed_time: Res<Time<Fixed>>,
+    game_metadata: Res<GameMetadata>,
+    mut ghost_delta_query: Query<&mut Text, With<GhostDelta>>,
+) {
+    let seconds = ghost_seconds(&fixed_time);
+    let ghost_score = if seconds <= ghost_run.replay.duration() {
+        ghost_run.replay.trace_at(seconds).map_or(0, |trace| trace.2)
+    } else {
+        ghost_run.replay.score
+    };
+
+    let delta = game_metadata.scores() as i64 - ghost_score as i64;
+    let label = format!("ghost {delta:+}");
+    for mut text in &mut ghost_delta_query {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
@@ -0,0 +1,68 @@
+pub mod file;
+pub mod ghost;
+pub mod resources;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, Time, Fixed},
+    app::{Startup, FixedUpdate, Update, Last},
+    log::{error, info},
+};
+
+use crate::{
+    menu::systems::skip_menu,
+    simulation::{resources::{GameRng, SimulationSettings}, systems::begin_tick, SimulationSet},
+    systems::cli_arg,
+};
+
+use self::{
+    file::{load_replay, Replay},
+    resources::{ReplayPlayback, ReplayRecorder},
+    systems::{
+        record_tick_input, record_trace, save_recorded_replay, adjust_playback_speed, play_back_input,
+        end_playback_on_game_over,
+    },
+};
+
+// every run is recorded into `replays/`. `--replay <file>` plays one back instead,
+// at the speed given with `--replay-speed <speed>`, changed with -/+ while watching, until its game over.
+// must be added after the `SimulationPlug`, the replay overrides its seed and tick rate
+pub struct ReplayPlug;
+
+impl Plugin for ReplayPlug {
+    fn build(&self, app: &mut App) {
+        let replay = cli_arg::<String>("--replay").and_then(|path| match load_replay(&path) {
+            Ok(replay) => Some(replay),
+            Err(err) => {
+                error!("{path}: {err}");
+                None
+            },
+        });
+
+        match replay {
+            Some(replay) => {
+                info!("playing back {} ticks", replay.ticks);
+                let speed = cli_arg::<f32>("--replay-speed").unwrap_or(1.);
+                app.insert_resource(Time::<Fixed>::from_hz(replay.tick_rate))
+                    .insert_resource(SimulationSettings { tick_rate: replay.tick_rate })
+                    .insert_resource(GameRng::from_seed(replay.seed))
+                    .insert_resource(replay.match_settings);
+                if let Some(challenge) = replay.challenge.clone() {
+                    app.insert_resource(challenge);
+                }
+                app.insert_resource(ReplayPlayback::new(replay, speed))
+                    .add_systems(Startup, skip_menu)
+                    .add_systems(Update, (adjust_playback_speed, end_playback_on_game_over))
+                    .add_systems(FixedUpdate, play_back_input.after(begin_tick).in_set(SimulationSet::Begin));
+            },
+            None => {
+                let seed = app.world.resource::<GameRng>().seed;
+                let tick_rate = app.world.resource::<SimulationSettings>().tick_rate;
+                app.insert_resource(ReplayRecorder { replay: Replay::new(seed, tick_rate) })
+                    .add_systems(FixedUpdate, record_tick_input.after(begin_tick).in_set(SimulationSet::Begin))
+                    .add_systems(FixedUpdate, record_trace.in_set(SimulationSet::End))
+                    .add_systems(Last, save_recorded_replay);
+            },
+        }
+    }
+}
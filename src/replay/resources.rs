@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use super::file::Replay;
+
+pub const MIN_PLAYBACK_SPEED: f32 = 0.5;
+pub const MAX_PLAYBACK_SPEED: f32 = 4.;
+
+// the run being played, saved once the game exits
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub replay: Replay,
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub cursor: (usize, u32),
+    pub tick: u32,
+    pub speed: f32,
+    pub finished: bool,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay, speed: f32) -> Self {
+        ReplayPlayback {
+            replay,
+            cursor: (0, 0),
+            tick: 0,
+            speed: speed.clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED),
+            finished: false,
+        }
+    }
+}
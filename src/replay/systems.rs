@@ -0,0 +1,142 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    background::resources::{GameMetadata, RunRules},
+    challenge::resources::DailyChallenge,
+    player::{components::Character, resources::MatchSettings},
+    simulation::resources::{GameRng, SimulationSettings, SimulationTick, TickInput},
+    systems::GameOver,
+};
+
+use super::{
+    file::{replay_path, save_replay, TracePoint},
+    resources::{ReplayPlayback, ReplayRecorder, MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED},
+};
+
+use bevy::{app::AppExit, prelude::*};
+
+pub fn record_tick_input(
+    mut recorder: ResMut<ReplayRecorder>,
+    tick_input: Res<TickInput>,
+    simulation_tick: Res<SimulationTick>,
+) {
+    recorder.replay.truncate(simulation_tick.0);
+    recorder.replay.push(&tick_input);
+}
+
+pub fn record_trace(
+    mut recorder: ResMut<ReplayRecorder>,
+    character_query: Query<(&Transform, &Character)>,
+    game_metadata: Res<GameMetadata>,
+) {
+    let replay = &mut recorder.replay;
+    if !replay.ticks.is_multiple_of(replay.trace_interval) {
+        return;
+    }
+    // the first player stands for the team
+    if let Some((transform, _)) = character_query.iter().find(|(_, character)| character.player == 0) {
+        replay.trace.push(TracePoint(transform.translation.x, transform.translation.y, game_metadata.scores()));
+    }
+}
+
+pub fn save_recorded_replay(
+    mut app_exit_event_reader: EventReader<AppExit>,
+    mut recorder: ResMut<ReplayRecorder>,
+    game_metadata: Res<GameMetadata>,
+    match_settings: Res<MatchSettings>,
+    game_rng: Res<GameRng>,
+    simulation_settings: Res<SimulationSettings>,
+    challenge: Option<Res<DailyChallenge>>,
+) {
+    if app_exit_event_reader.read().last().is_none() || recorder.replay.ticks == 0 {
+        return;
+    }
+
+    recorder.replay.score = game_metadata.scores();
+    recorder.replay.match_settings = *match_settings;
+    // an online match plays with the settings of the host
+    recorder.replay.seed = game_rng.seed;
+    recorder.replay.tick_rate = simulation_settings.tick_rate;
+    recorder.replay.challenge = challenge.map(|challenge| challenge.clone());
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let path = replay_path(format!("{timestamp}-{}", recorder.replay.seed));
+    match save_replay(&recorder.replay, &path) {
+        Ok(()) => info!("replay saved to {path}"),
+        Err(err) => error!("{err}"),
+    }
+    // the exit event may be read again before the app actually closes
+    recorder.replay.ticks = 0;
+}
+
+// the playback speed goes on top of the speed of the run, see `set_game_speed`
+pub fn adjust_playback_speed(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    run_rules: Res<RunRules>,
+) {
+    let speed = if keyboard_input.any_just_pressed([KeyCode::Equals, KeyCode::NumpadAdd]) {
+        playback.speed * 2.
+    } else if keyboard_input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        playback.speed / 2.
+    } else {
+        return;
+    };
+
+    playback.speed = speed.clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED);
+    virtual_time.set_relative_speed(playback.speed * run_rules.game_speed);
+    info!("replay speed {}x", playback.speed);
+}
+
+// the recorded input replaces the keyboard, and once it runs out the final score is checked
+pub fn play_back_input(
+    mut playback: ResMut<ReplayPlayback>,
+    mut tick_input: ResMut<TickInput>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+    game_metadata: Res<GameMetadata>,
+) {
+    if playback.finished {
+        *tick_input = TickInput::default();
+        return;
+    }
+
+    let playback = playback.as_mut();
+    if let Some(input) = playback.replay.next_input(&mut playback.cursor) {
+        *tick_input = input;
+        playback.tick += 1;
+        return;
+    }
+
+    *tick_input = TickInput::default();
+    finish_playback(playback, &game_metadata, &mut app_exit_event_writer);
+}
+
+// the run is over at its game over, even if ticks were recorded past it
+pub fn end_playback_on_game_over(
+    mut game_over_event_reader: EventReader<GameOver>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
+    game_metadata: Res<GameMetadata>,
+) {
+    if game_over_event_reader.read().last().is_none() || playback.finished {
+        return;
+    }
+    finish_playback(&mut playback, &game_metadata, &mut app_exit_event_writer);
+}
+
+fn finish_playback(playback: &mut ReplayPlayback, game_metadata: &GameMetadata, app_exit_event_writer: &mut EventWriter<AppExit>) {
+    playback.finished = true;
+    let scores = game_metadata.scores();
+    if scores == playback.replay.score {
+        info!("replay verified: {} ticks, score {}", playback.tick, scores);
+    } else {
+        warn!("replay diverged: recorded score {}, played back score {}", playback.replay.score, scores);
+    }
+    app_exit_event_writer.send(AppExit);
+}
+
+pub fn replay_playing(playback: Option<Res<ReplayPlayback>>) -> bool {
+    playback.is_some()
+}
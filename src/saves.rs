@@ -0,0 +1,41 @@
+use std::fs;
+
+use bevy::log::{error, warn};
+use serde::{de::DeserializeOwned, Serialize};
+
+// progress kept from one run to the next, as ron files
+pub const SAVE_DIR: &str = "saves";
+
+fn save_path(name: &str) -> String {
+    format!("{SAVE_DIR}/{name}.ron")
+}
+
+// a missing save is a fresh start, a broken one is reported and started over
+pub fn load_save<T: DeserializeOwned + Default>(name: &str) -> T {
+    let path = save_path(name);
+    match fs::read_to_string(&path) {
+        Ok(content) => ron::from_str(&content).unwrap_or_else(|err| {
+            warn!("{path}: could not parse save: {err}");
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+pub fn write_save<T: Serialize>(name: &str, value: &T) {
+    let path = save_path(name);
+    let result = fs::create_dir_all(SAVE_DIR)
+        .map_err(|err| err.to_string())
+        .and_then(|_| ron::ser::to_string_pretty(value, Default::default()).map_err(|err| err.to_string()))
+        .and_then(|content| fs::write(&path, content).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        error!("{path}: could not write save: {err}");
+    }
+}
+
+// whoever is logged in, so several people sharing a machine each keep their own scores
+pub fn player_name() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "player".to_string())
+}
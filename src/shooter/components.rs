@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::objects::{components::{CollisionObject, HitboxSource}, hitbox::HitboxKind};
+
+#[derive(Component, Clone, Copy)]
+pub struct Projectile {
+    // the player who fired it, and earns the points
+    pub player: usize,
+    pub speed: f32,
+    pub size: Vec3,
+}
+
+impl CollisionObject for Projectile {
+    fn get_dimension(&self) -> (f32, f32) {
+        (self.size.x, self.size.y)
+    }
+}
+
+impl HitboxSource for Projectile {
+    fn hitbox_kind(&self) -> HitboxKind {
+        HitboxKind::Projectile
+    }
+}
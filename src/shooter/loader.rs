@@ -0,0 +1,16 @@
+use crate::data::RonAsset;
+
+use super::resources::ShooterSettings;
+
+impl RonAsset for ShooterSettings {
+    const NAME: &'static str = "shooter settings";
+    const EXTENSIONS: &'static [&'static str] = &["shooter.ron"];
+
+    fn validate(&mut self) -> Result<(), String> {
+        // the fire cooldown and the reload are timers of that many seconds
+        if self.fire_rate <= 0. || self.reload_duration <= 0. {
+            return Err("fire_rate and reload_duration must be positive".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+pub mod components;
+pub mod loader;
+pub mod resources;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, Condition, AssetEvent, resource_exists, on_event},
+    app::{Startup, Update, FixedUpdate},
+};
+
+use crate::{
+    collision::{CollisionSet, systems::impact_flash}, data::RonAssetApp, objects::systems::resolve_hitboxes,
+    simulation::{SimulationSet, resources::PendingAssets, rollback::RollbackApp, systems::release_simulation}
+};
+
+use self::{
+    components::Projectile,
+    resources::{Ammo, ShooterSettings},
+    systems::{
+        load_shooter_settings, apply_shooter_settings, toggle_shooter_mode, reload_ammo, fire_projectile, projectile_movement, projectile_cleanup, projectile_hit,
+        shooter_enabled
+    },
+};
+
+pub struct ShooterPlug;
+
+impl Plugin for ShooterPlug {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<ShooterSettings>()
+            .init_resource::<ShooterSettings>()
+            .init_resource::<Ammo>()
+            .rollback_component::<Projectile>()
+            .rollback_resource::<ShooterSettings>()
+            .rollback_resource::<Ammo>()
+            .add_systems(Startup, load_shooter_settings)
+            // kept up to date until the simulation starts, so its first tick already plays by the file
+            .add_systems(Update, apply_shooter_settings
+                .run_if(resource_exists::<PendingAssets>().or_else(on_event::<AssetEvent<ShooterSettings>>()))
+                .before(release_simulation))
+            .add_systems(FixedUpdate, resolve_hitboxes::<Projectile>.in_set(SimulationSet::Begin))
+            .add_systems(FixedUpdate, toggle_shooter_mode.after(SimulationSet::Begin))
+            .add_systems(FixedUpdate, (reload_ammo, fire_projectile).chain().run_if(shooter_enabled).after(toggle_shooter_mode).before(CollisionSet::Movement))
+            .add_systems(FixedUpdate, projectile_movement.in_set(CollisionSet::Movement))
+            .add_systems(FixedUpdate, projectile_hit.in_set(CollisionSet::Reaction).after(impact_flash))
+            .add_systems(FixedUpdate, projectile_cleanup.after(CollisionSet::Reaction));
+    }
+}
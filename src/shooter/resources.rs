@@ -0,0 +1,66 @@
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+const SHOOTER_DEFAULT_FIRE_RATE: f32 = 4.;
+const SHOOTER_DEFAULT_MAX_AMMO: u32 = 10;
+const SHOOTER_DEFAULT_RELOAD_DURATION: f32 = 0.75;
+const SHOOTER_DEFAULT_ENEMY_SCORE: u32 = 5;
+
+// tuned in `assets/data/default.shooter.ron`, these defaults stand in until it is loaded or when it cannot be
+#[derive(Resource, Asset, TypePath, Deserialize, Clone, Copy, PartialEq)]
+pub struct ShooterSettings {
+    // switched on and off during the run, never read from the file
+    #[serde(skip)]
+    pub enabled: bool,
+    // shots per second
+    pub fire_rate: f32,
+    pub max_ammo: u32,
+    // seconds to get one shot back
+    pub reload_duration: f32,
+    // points for shooting down an enemy
+    pub enemy_score: u32,
+}
+
+impl Default for ShooterSettings {
+    fn default() -> Self {
+        ShooterSettings {
+            enabled: false,
+            fire_rate: SHOOTER_DEFAULT_FIRE_RATE,
+            max_ammo: SHOOTER_DEFAULT_MAX_AMMO,
+            reload_duration: SHOOTER_DEFAULT_RELOAD_DURATION,
+            enemy_score: SHOOTER_DEFAULT_ENEMY_SCORE,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ShooterSettingsHandle {
+    pub handle: Handle<ShooterSettings>,
+}
+
+#[derive(Resource, Clone)]
+pub struct Ammo {
+    pub count: u32,
+    pub fire_cooldown: Timer,
+    pub reload_timer: Timer,
+}
+
+impl Ammo {
+    pub fn new(settings: &ShooterSettings) -> Self {
+        let mut fire_cooldown = Timer::from_seconds(1. / settings.fire_rate, TimerMode::Once);
+        // ready to fire straight away
+        fire_cooldown.tick(fire_cooldown.duration());
+        Ammo {
+            count: settings.max_ammo,
+            fire_cooldown,
+            reload_timer: Timer::from_seconds(settings.reload_duration, TimerMode::Repeating),
+        }
+    }
+}
+
+impl FromWorld for Ammo {
+    fn from_world(world: &mut World) -> Self {
+        let settings = world.get_resource_or_insert_with(ShooterSettings::default);
+        Ammo::new(&settings)
+    }
+}
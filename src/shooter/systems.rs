@@ -0,0 +1,189 @@
+use crate::{
+    background::resources::GameBonudary,
+    collision::{components::CollisionProbe, events::CollisionEvent},
+    objects::components::{UfoType, Ufo},
+    simulation::{resources::{PendingAssets, TickInput}, rollback::Rollback},
+    player::{components::Character, events::{EarnPointEvent, PointSource}, systems::CHARACTER_HEIGHT},
+};
+
+use super::{
+    components::Projectile,
+    resources::{Ammo, ShooterSettings, ShooterSettingsHandle},
+};
+
+use bevy::{prelude::*, utils::HashSet};
+
+const PROJECTILE_SPEED: f32 = 600.;
+const PROJECTILE_WIDTH: f32 = 8.;
+const PROJECTILE_HEIGHT: f32 = 20.;
+
+pub fn load_shooter_settings(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pending_assets: ResMut<PendingAssets>,
+) {
+    let handle: Handle<ShooterSettings> = asset_server.load("data/default.shooter.ron");
+    pending_assets.handles.push(handle.clone().untyped());
+    commands.insert_resource(ShooterSettingsHandle { handle });
+}
+
+// the shooter stays on or off as it was, with a full load of ammo
+pub fn apply_shooter_settings(
+    mut shooter_settings: ResMut<ShooterSettings>,
+    mut ammo: ResMut<Ammo>,
+    settings_assets: Res<Assets<ShooterSettings>>,
+    settings_handle: Res<ShooterSettingsHandle>,
+) {
+    let Some(settings) = settings_assets.get(&settings_handle.handle) else {
+        return;
+    };
+    let settings = ShooterSettings {
+        enabled: shooter_settings.enabled,
+        ..*settings
+    };
+    if *shooter_settings != settings {
+        *shooter_settings = settings;
+        *ammo = Ammo::new(&shooter_settings);
+    }
+}
+
+pub fn shooter_enabled(shooter_settings: Res<ShooterSettings>) -> bool {
+    shooter_settings.enabled
+}
+
+pub fn toggle_shooter_mode(
+    tick_input: Res<TickInput>,
+    mut shooter_settings: ResMut<ShooterSettings>,
+    mut ammo: ResMut<Ammo>,
+) {
+    if tick_input.toggle_shooter {
+        shooter_settings.enabled = !shooter_settings.enabled;
+        *ammo = Ammo::new(&shooter_settings);
+    }
+}
+
+pub fn reload_ammo(
+    mut ammo: ResMut<Ammo>,
+    shooter_settings: Res<ShooterSettings>,
+    time: Res<Time>,
+) {
+    ammo.fire_cooldown.tick(time.delta());
+    if ammo.count >= shooter_settings.max_ammo {
+        ammo.reload_timer.reset();
+        return;
+    }
+
+    ammo.reload_timer.tick(time.delta());
+    if ammo.reload_timer.just_finished() {
+        ammo.count += 1;
+    }
+}
+
+pub fn fire_projectile(
+    mut commands: Commands,
+    mut ammo: ResMut<Ammo>,
+    tick_input: Res<TickInput>,
+    character_query: Query<(&Transform, &Character)>,
+) {
+    // the players share the ammo
+    for (char_transform, character) in character_query.iter() {
+        if !tick_input.players[character.player].fire || !ammo.fire_cooldown.finished() || ammo.count == 0 {
+            continue;
+        }
+
+        ammo.count -= 1;
+        ammo.fire_cooldown.reset();
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(1., 0.9, 0.3),
+                    custom_size: Some(Vec2::new(PROJECTILE_WIDTH, PROJECTILE_HEIGHT)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    char_transform.translation + Vec3::new(0., CHARACTER_HEIGHT / 2., 1.)
+                ),
+                ..default()
+            },
+            Projectile {
+                player: character.player,
+                speed: PROJECTILE_SPEED,
+                size: Vec3::new(PROJECTILE_WIDTH, PROJECTILE_HEIGHT, 0.),
+            },
+            CollisionProbe,
+            Rollback,
+        ));
+    }
+}
+
+pub fn projectile_movement(
+    mut projectile_query: Query<(&mut Transform, &Projectile)>,
+    time: Res<Time>,
+) {
+    for (mut transform, projectile) in projectile_query.iter_mut() {
+        transform.translation.y += projectile.speed * time.delta_seconds();
+    }
+}
+
+pub fn projectile_cleanup(
+    mut commands: Commands,
+    projectile_query: Query<(Entity, &Transform), With<Projectile>>,
+    game_boundary: Res<GameBonudary>,
+) {
+    for (entity, transform) in projectile_query.iter() {
+        if transform.translation.y > game_boundary.y_max {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// a projectile only takes down the first ufo it runs into, and an ufo only counts once
+pub fn projectile_hits<'a>(
+    collision_events: impl IntoIterator<Item = &'a CollisionEvent>,
+    is_projectile: impl Fn(Entity) -> bool,
+    is_ufo: impl Fn(Entity) -> bool,
+) -> Vec<&'a CollisionEvent> {
+    let mut spent: HashSet<Entity> = HashSet::new();
+    collision_events.into_iter()
+        .filter(|event| is_projectile(event.a) && is_ufo(event.b))
+        .filter(|event| {
+            let fresh = !spent.contains(&event.a) && !spent.contains(&event.b);
+            if fresh {
+                spent.insert(event.a);
+                spent.insert(event.b);
+            }
+            fresh
+        })
+        .collect()
+}
+
+// enemies shot down are worth points, bonuses shot by mistake are simply lost.
+// the ufo that took the hit is despawned along with the other collided ones
+pub fn projectile_hit(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut earn_point_event: EventWriter<EarnPointEvent>,
+    projectile_query: Query<&Projectile>,
+    ufo_query: Query<&Ufo>,
+    shooter_settings: Res<ShooterSettings>,
+) {
+    let hits = projectile_hits(
+        collision_events.read(),
+        |entity| projectile_query.contains(entity),
+        |entity| ufo_query.contains(entity),
+    );
+    for event in hits {
+        let (Ok(projectile), Ok(ufo)) = (projectile_query.get(event.a), ufo_query.get(event.b)) else {
+            continue;
+        };
+        if let UfoType::Enemy = ufo.kind {
+            earn_point_event.send(EarnPointEvent {
+                player: projectile.player,
+                scores: shooter_settings.enemy_score,
+                source: PointSource::Enemy,
+            });
+        }
+        commands.entity(event.a).despawn();
+    }
+}
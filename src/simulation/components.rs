@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+// simulated translation of the last two ticks, rendered in between
+#[derive(Component, Clone, Copy)]
+pub struct Interpolated {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
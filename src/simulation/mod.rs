@@ -0,0 +1,74 @@
+pub mod components;
+pub mod resources;
+pub mod rollback;
+pub mod systems;
+
+use bevy::{
+    prelude::{
+        Plugin, App, IntoSystemConfigs, IntoSystemSetConfigs, SystemSet, Time, Fixed, Startup, PreUpdate, FixedUpdate,
+        Update, PostUpdate, Transform, Sprite, Handle, Image, resource_exists, in_state
+    },
+    input::InputSystem,
+    transform::TransformSystem,
+};
+
+use crate::{collision::CollisionSet, systems::{cli_arg, AppState}};
+
+use self::{
+    components::Interpolated,
+    resources::{SimulationSettings, GameRng, ActionInput, TickInput, PendingAssets, SimulationTick},
+    rollback::{RollbackApp, RollbackIds, Snapshots, save_snapshot},
+    systems::{
+        sample_action_input, begin_tick, end_tick, restore_simulated_translation, store_simulated_translation,
+        interpolate_translation, hold_simulation, release_simulation, log_seed
+    },
+};
+
+// every tick starts from the simulated state and its input, and ends by saving the simulated state.
+// when rollback is on, the whole state is snapshot before anything else of the tick
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    Snapshot,
+    Begin,
+    End,
+}
+
+// gameplay runs in `FixedUpdate` at a steady tick rate, so it plays the same at any frame rate.
+// the tick rate and the seed can be given with `--tick-rate <hz>` and `--seed <seed>`
+pub struct SimulationPlug;
+
+impl Plugin for SimulationPlug {
+    fn build(&self, app: &mut App) {
+        let mut settings = SimulationSettings::default();
+        if let Some(tick_rate) = cli_arg::<f64>("--tick-rate").filter(|tick_rate| *tick_rate > 0.) {
+            settings.tick_rate = tick_rate;
+        }
+        let rng = cli_arg::<u64>("--seed").map_or_else(GameRng::default, GameRng::from_seed);
+
+        app.insert_resource(Time::<Fixed>::from_hz(settings.tick_rate))
+            .insert_resource(settings)
+            .insert_resource(rng)
+            .init_resource::<ActionInput>()
+            .init_resource::<TickInput>()
+            .init_resource::<PendingAssets>()
+            .init_resource::<SimulationTick>()
+            .init_resource::<RollbackIds>()
+            .rollback_component::<Transform>()
+            .rollback_component::<Sprite>()
+            .rollback_component::<Handle<Image>>()
+            .rollback_component::<Interpolated>()
+            .rollback_resource::<GameRng>()
+            .rollback_resource::<SimulationTick>()
+            .rollback_resource::<RollbackIds>()
+            .configure_sets(FixedUpdate, SimulationSet::Snapshot.before(SimulationSet::Begin))
+            .configure_sets(FixedUpdate, SimulationSet::Begin.before(CollisionSet::History))
+            .configure_sets(FixedUpdate, SimulationSet::End.after(CollisionSet::Reaction))
+            .add_systems(Startup, (hold_simulation, log_seed))
+            .add_systems(Update, release_simulation.run_if(resource_exists::<PendingAssets>()).run_if(in_state(AppState::Playing)))
+            .add_systems(PreUpdate, sample_action_input.after(InputSystem))
+            .add_systems(FixedUpdate, save_snapshot.run_if(resource_exists::<Snapshots>()).in_set(SimulationSet::Snapshot))
+            .add_systems(FixedUpdate, (begin_tick, restore_simulated_translation).in_set(SimulationSet::Begin))
+            .add_systems(FixedUpdate, (store_simulated_translation, end_tick).in_set(SimulationSet::End))
+            .add_systems(PostUpdate, interpolate_translation.before(TransformSystem::TransformPropagate));
+    }
+}
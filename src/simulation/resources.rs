@@ -0,0 +1,157 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+pub const DEFAULT_TICK_RATE: f64 = 60.;
+
+#[derive(Resource)]
+pub struct SimulationSettings {
+    // gameplay ticks per second
+    pub tick_rate: f64,
+}
+
+impl Default for SimulationSettings {
+    fn default() -> Self {
+        SimulationSettings {
+            tick_rate: DEFAULT_TICK_RATE,
+        }
+    }
+}
+
+// every random draw of the gameplay goes through this, so a seed replays the same run
+#[derive(Resource, Clone)]
+pub struct GameRng {
+    pub seed: u64,
+    pub rng: StdRng,
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64);
+        GameRng::from_seed(seed)
+    }
+}
+
+pub const MAX_PLAYERS: usize = 2;
+
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub dash: KeyCode,
+    pub fire: KeyCode,
+}
+
+// both players share one keyboard, the first one on the arrows and the second one on wasd
+pub const PLAYER_KEYS: [KeyBindings; MAX_PLAYERS] = [
+    KeyBindings {
+        up: KeyCode::Up,
+        down: KeyCode::Down,
+        left: KeyCode::Left,
+        right: KeyCode::Right,
+        dash: KeyCode::Space,
+        fire: KeyCode::Z,
+    },
+    KeyBindings {
+        up: KeyCode::W,
+        down: KeyCode::S,
+        left: KeyCode::A,
+        right: KeyCode::D,
+        dash: KeyCode::ShiftLeft,
+        fire: KeyCode::Q,
+    },
+];
+
+// buttons of a player packed in a byte, for replays and the network
+const UP: u8 = 1;
+const DOWN: u8 = 1 << 1;
+const LEFT: u8 = 1 << 2;
+const RIGHT: u8 = 1 << 3;
+const DASH: u8 = 1 << 4;
+const FIRE: u8 = 1 << 5;
+// the shooter toggle rides along with the buttons of whoever pressed it
+pub const TOGGLE_SHOOTER: u8 = 1 << 6;
+
+#[derive(Default, Clone, Copy, PartialEq)]
+pub struct PlayerInput {
+    pub movement: Vec2,
+    pub dash: bool,
+    pub fire: bool,
+}
+
+impl PlayerInput {
+    pub fn to_bits(self) -> u8 {
+        let mut buttons = 0;
+        if self.movement.y > 0. {
+            buttons |= UP;
+        }
+        if self.movement.y < 0. {
+            buttons |= DOWN;
+        }
+        if self.movement.x < 0. {
+            buttons |= LEFT;
+        }
+        if self.movement.x > 0. {
+            buttons |= RIGHT;
+        }
+        if self.dash {
+            buttons |= DASH;
+        }
+        if self.fire {
+            buttons |= FIRE;
+        }
+        buttons
+    }
+
+    pub fn from_bits(buttons: u8) -> Self {
+        let axis = |positive: u8, negative: u8| match (buttons & positive != 0, buttons & negative != 0) {
+            (true, false) => 1.,
+            (false, true) => -1.,
+            _ => 0.,
+        };
+        PlayerInput {
+            movement: Vec2::new(axis(RIGHT, LEFT), axis(UP, DOWN)),
+            dash: buttons & DASH != 0,
+            fire: buttons & FIRE != 0,
+        }
+    }
+}
+
+// keyboard state gathered every frame, until the next tick picks it up.
+// presses are latched so a short tap between two ticks is not lost
+#[derive(Resource, Default, Clone, Copy)]
+pub struct ActionInput {
+    pub players: [PlayerInput; MAX_PLAYERS],
+    pub toggle_shooter: bool,
+}
+
+// the actions the gameplay sees during one tick
+#[derive(Resource, Default, Clone, Copy, PartialEq)]
+pub struct TickInput {
+    pub players: [PlayerInput; MAX_PLAYERS],
+    pub toggle_shooter: bool,
+}
+
+// number of the tick being simulated, counted from the start of the match
+#[derive(Resource, Default, Clone, Copy)]
+pub struct SimulationTick(pub u32);
+
+// assets the gameplay depends on, e.g. alpha mask hitboxes and wave files.
+// the simulation holds still until they are all done loading, so every run starts on the same tick
+#[derive(Resource, Default)]
+pub struct PendingAssets {
+    pub handles: Vec<UntypedHandle>,
+}
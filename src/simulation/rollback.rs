@@ -0,0 +1,302 @@
+use std::{any::{Any, TypeId}, collections::VecDeque};
+
+use bevy::{
+    prelude::*,
+    render::primitives::Aabb,
+    utils::{HashMap, HashSet},
+};
+
+use super::resources::SimulationTick;
+
+// ticks kept around to roll back to
+pub const SNAPSHOT_CAPACITY: usize = 64;
+
+// entities that are part of the simulated state, e.g. characters and ufos
+#[derive(Component, Clone, Copy)]
+pub struct Rollback;
+
+// the same entity across a rollback, entity ids are not kept when an entity is spawned again
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RollbackId(pub u32);
+
+#[derive(Resource, Default, Clone, Copy)]
+pub struct RollbackIds {
+    pub next: u32,
+}
+
+// the simulated state at the start of a tick
+pub struct Snapshot {
+    pub tick: u32,
+    entities: Vec<RollbackId>,
+    data: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+type SaveFn = fn(&World, &mut Snapshot);
+type LoadFn = fn(&mut World, &Snapshot);
+
+// how to save and load every type that is part of the simulated state
+#[derive(Resource, Default)]
+pub struct RollbackRegistry {
+    types: Vec<(SaveFn, LoadFn)>,
+    components: HashSet<TypeId>,
+}
+
+// only kept while something may need to roll back, e.g. an online match
+#[derive(Resource, Default)]
+pub struct Snapshots {
+    pub ring: VecDeque<Snapshot>,
+}
+
+pub trait RollbackApp {
+    fn rollback_component<T: Component + Clone>(&mut self) -> &mut Self;
+    fn rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self;
+}
+
+impl RollbackApp for App {
+    fn rollback_component<T: Component + Clone>(&mut self) -> &mut Self {
+        let mut registry = self.world.get_resource_or_insert_with(RollbackRegistry::default);
+        registry.types.push((save_component::<T>, load_component::<T>));
+        registry.components.insert(TypeId::of::<T>());
+        self
+    }
+
+    fn rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        self.world.get_resource_or_insert_with(RollbackRegistry::default)
+            .types.push((save_resource::<R>, load_resource::<R>));
+        self
+    }
+}
+
+fn save_component<T: Component + Clone>(world: &World, snapshot: &mut Snapshot) {
+    let values: HashMap<RollbackId, T> = world.iter_entities()
+        .filter_map(|entity| Some((*entity.get::<RollbackId>()?, entity.get::<T>()?.clone())))
+        .collect();
+    snapshot.data.insert(TypeId::of::<T>(), Box::new(values));
+}
+
+fn load_component<T: Component + Clone>(world: &mut World, snapshot: &Snapshot) {
+    let Some(values) = snapshot.data.get(&TypeId::of::<T>()).and_then(|values| values.downcast_ref::<HashMap<RollbackId, T>>()) else {
+        return;
+    };
+    for (entity, id) in rollback_entities(world) {
+        match values.get(&id) {
+            Some(value) => world.entity_mut(entity).insert(value.clone()),
+            None => world.entity_mut(entity).remove::<T>(),
+        };
+    }
+}
+
+fn save_resource<R: Resource + Clone>(world: &World, snapshot: &mut Snapshot) {
+    if let Some(resource) = world.get_resource::<R>() {
+        snapshot.data.insert(TypeId::of::<R>(), Box::new(resource.clone()));
+    }
+}
+
+fn load_resource<R: Resource + Clone>(world: &mut World, snapshot: &Snapshot) {
+    match snapshot.data.get(&TypeId::of::<R>()).and_then(|resource| resource.downcast_ref::<R>()) {
+        Some(resource) => world.insert_resource(resource.clone()),
+        None => {
+            world.remove_resource::<R>();
+        },
+    }
+}
+
+fn rollback_entities(world: &mut World) -> Vec<(Entity, RollbackId)> {
+    world.query::<(Entity, &RollbackId)>().iter(world).map(|(entity, id)| (entity, *id)).collect()
+}
+
+// new entities are numbered by where they are, so every peer numbers them the same way
+pub fn assign_rollback_ids(world: &mut World) {
+    let mut new_entities: Vec<(Entity, [u32; 3])> = world
+        .query_filtered::<(Entity, &Transform), (With<Rollback>, Without<RollbackId>)>()
+        .iter(world)
+        .map(|(entity, transform)| (entity, transform.translation.to_array().map(f32::to_bits)))
+        .collect();
+    new_entities.sort_by_key(|(_, position)| *position);
+
+    let mut ids = world.get_resource_or_insert_with(RollbackIds::default);
+    let first = ids.next;
+    ids.next += new_entities.len() as u32;
+    for (index, (entity, _)) in new_entities.into_iter().enumerate() {
+        world.entity_mut(entity).insert(RollbackId(first + index as u32));
+    }
+}
+
+// what an entity spawned back by `load_entities` gets without being registered
+fn rebuilt_components() -> [TypeId; 7] {
+    [
+        TypeId::of::<Rollback>(),
+        TypeId::of::<RollbackId>(),
+        TypeId::of::<GlobalTransform>(),
+        TypeId::of::<Visibility>(),
+        TypeId::of::<InheritedVisibility>(),
+        TypeId::of::<ViewVisibility>(),
+        TypeId::of::<Aabb>(),
+    ]
+}
+
+// a component missing from the registry would be silently lost on every rollback
+#[cfg(debug_assertions)]
+fn assert_registered(world: &mut World) {
+    let entities = rollback_entities(world);
+    let registry = world.resource::<RollbackRegistry>();
+    let rebuilt = rebuilt_components();
+    for (entity, _) in entities {
+        for info in world.inspect_entity(entity) {
+            let registered = info.type_id().is_some_and(|id| registry.components.contains(&id) || rebuilt.contains(&id));
+            assert!(registered, "rollback entity has the unregistered component {}", info.name());
+        }
+    }
+}
+
+pub fn save_snapshot(world: &mut World) {
+    assign_rollback_ids(world);
+    #[cfg(debug_assertions)]
+    assert_registered(world);
+
+    let tick = world.resource::<SimulationTick>().0;
+    let mut snapshot = Snapshot {
+        tick,
+        entities: rollback_entities(world).into_iter().map(|(_, id)| id).collect(),
+        data: HashMap::new(),
+    };
+    for (save, _) in world.resource::<RollbackRegistry>().types.iter() {
+        save(world, &mut snapshot);
+    }
+
+    let mut snapshots = world.resource_mut::<Snapshots>();
+    // ticks played again after a rollback replace the old ones
+    snapshots.ring.retain(|snapshot| snapshot.tick < tick);
+    if snapshots.ring.len() >= SNAPSHOT_CAPACITY {
+        snapshots.ring.pop_front();
+    }
+    snapshots.ring.push_back(snapshot);
+}
+
+// put the simulated state back to the start of `tick`, returns false if that tick is too old
+pub fn load_snapshot(world: &mut World, tick: u32) -> bool {
+    // entities spawned since the last snapshot need an id to be despawned
+    assign_rollback_ids(world);
+
+    let Some(mut snapshots) = world.remove_resource::<Snapshots>() else {
+        return false;
+    };
+    let loaded = match snapshots.ring.iter().find(|snapshot| snapshot.tick == tick) {
+        Some(snapshot) => {
+            load_entities(world, snapshot);
+            let types: Vec<LoadFn> = world.resource::<RollbackRegistry>().types.iter().map(|(_, load)| *load).collect();
+            for load in types {
+                load(world, snapshot);
+            }
+            true
+        },
+        None => false,
+    };
+    if loaded {
+        snapshots.ring.retain(|snapshot| snapshot.tick <= tick);
+    }
+    world.insert_resource(snapshots);
+    loaded
+}
+
+// despawn what did not exist yet and spawn back what has been despawned since,
+// the components are put back by the registered types
+fn load_entities(world: &mut World, snapshot: &Snapshot) {
+    let wanted: HashSet<RollbackId> = snapshot.entities.iter().copied().collect();
+    let mut present: HashSet<RollbackId> = HashSet::new();
+    for (entity, id) in rollback_entities(world) {
+        if wanted.contains(&id) {
+            present.insert(id);
+        } else {
+            world.entity_mut(entity).despawn_recursive();
+        }
+    }
+
+    for id in snapshot.entities.iter().filter(|id| !present.contains(*id)) {
+        world.spawn((SpriteBundle::default(), Rollback, *id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component, Clone)]
+    struct Unregistered;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.rollback_component::<Transform>()
+            .rollback_component::<Sprite>()
+            .rollback_component::<Handle<Image>>()
+            .rollback_resource::<SimulationTick>()
+            .init_resource::<SimulationTick>()
+            .init_resource::<Snapshots>();
+        app
+    }
+
+    fn spawn_at(world: &mut World, x: f32) -> Entity {
+        world.spawn((SpriteBundle { transform: Transform::from_xyz(x, 0., 0.), ..default() }, Rollback)).id()
+    }
+
+    fn translations(world: &mut World) -> Vec<f32> {
+        let mut translations: Vec<f32> = world.query_filtered::<&Transform, With<Rollback>>()
+            .iter(world)
+            .map(|transform| transform.translation.x)
+            .collect();
+        translations.sort_by(f32::total_cmp);
+        translations
+    }
+
+    #[test]
+    fn new_entities_are_numbered_by_position() {
+        let ids = |positions: [f32; 2]| {
+            let mut app = app();
+            for x in positions {
+                spawn_at(&mut app.world, x);
+            }
+            assign_rollback_ids(&mut app.world);
+            let mut ids: Vec<(i32, RollbackId)> = app.world.query::<(&Transform, &RollbackId)>()
+                .iter(&app.world)
+                .map(|(transform, id)| (transform.translation.x as i32, *id))
+                .collect();
+            ids.sort();
+            ids
+        };
+        // spawned in any order, the same places get the same ids
+        assert!(ids([-10., 10.]) == ids([10., -10.]));
+    }
+
+    #[test]
+    fn loading_puts_the_entities_back() {
+        let mut app = app();
+        let moved = spawn_at(&mut app.world, 0.);
+        let despawned = spawn_at(&mut app.world, 20.);
+        save_snapshot(&mut app.world);
+
+        app.world.get_mut::<Transform>(moved).unwrap().translation.x = 5.;
+        app.world.despawn(despawned);
+        spawn_at(&mut app.world, 40.);
+        app.world.resource_mut::<SimulationTick>().0 = 3;
+
+        assert!(load_snapshot(&mut app.world, 0));
+        assert_eq!(translations(&mut app.world), vec![0., 20.]);
+        assert_eq!(app.world.resource::<SimulationTick>().0, 0);
+    }
+
+    #[test]
+    fn ticks_no_longer_kept_cannot_be_loaded() {
+        let mut app = app();
+        save_snapshot(&mut app.world);
+        assert!(!load_snapshot(&mut app.world, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unregistered")]
+    fn unregistered_components_are_caught() {
+        let mut app = app();
+        let entity = spawn_at(&mut app.world, 0.);
+        app.world.entity_mut(entity).insert(Unregistered);
+        save_snapshot(&mut app.world);
+    }
+}
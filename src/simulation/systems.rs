@@ -0,0 +1,122 @@
+use super::{
+    components::Interpolated,
+    resources::{ActionInput, TickInput, PendingAssets, GameRng, SimulationTick, PLAYER_KEYS},
+};
+
+use crate::objects::hitbox::Hitbox;
+
+use bevy::{asset::LoadState, prelude::*};
+
+pub fn log_seed(game_rng: Res<GameRng>) {
+    info!("simulation seed {}", game_rng.seed);
+}
+
+pub fn hold_simulation(mut virtual_time: ResMut<Time<Virtual>>) {
+    virtual_time.pause();
+}
+
+// runs once the match has started from the menu.
+// a failed asset counts as done, the gameplay has a fallback for each of them
+pub fn release_simulation(
+    mut commands: Commands,
+    mut pending_assets: ResMut<PendingAssets>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    asset_server: Res<AssetServer>,
+) {
+    pending_assets.handles.retain(|handle| {
+        !matches!(asset_server.load_state(handle.id()), LoadState::Loaded | LoadState::Failed)
+    });
+    if pending_assets.handles.is_empty() {
+        virtual_time.unpause();
+        commands.remove_resource::<PendingAssets>();
+    }
+}
+
+pub fn sample_action_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut action_input: ResMut<ActionInput>,
+) {
+    for (player_input, keys) in action_input.players.iter_mut().zip(PLAYER_KEYS.iter()) {
+        let mut movement = Vec2::ZERO;
+
+        if keyboard_input.pressed(keys.up) {
+            movement.y += 1.;
+        }
+
+        if keyboard_input.pressed(keys.down) {
+            movement.y -= 1.;
+        }
+
+        if keyboard_input.pressed(keys.left) {
+            movement.x -= 1.;
+        }
+
+        if keyboard_input.pressed(keys.right) {
+            movement.x += 1.;
+        }
+
+        player_input.movement = movement;
+        player_input.dash |= keyboard_input.just_pressed(keys.dash);
+        player_input.fire = keyboard_input.pressed(keys.fire);
+    }
+    action_input.toggle_shooter |= keyboard_input.just_pressed(KeyCode::F1);
+}
+
+// hand the input gathered since the last tick to the gameplay
+pub fn begin_tick(
+    mut action_input: ResMut<ActionInput>,
+    mut tick_input: ResMut<TickInput>,
+) {
+    *tick_input = TickInput {
+        players: action_input.players,
+        toggle_shooter: action_input.toggle_shooter,
+    };
+    for player_input in action_input.players.iter_mut() {
+        player_input.dash = false;
+    }
+    action_input.toggle_shooter = false;
+}
+
+pub fn end_tick(mut simulation_tick: ResMut<SimulationTick>) {
+    simulation_tick.0 += 1;
+}
+
+// undo the render interpolation, the gameplay works on the simulated translation
+pub fn restore_simulated_translation(
+    mut object_query: Query<(&mut Transform, &Interpolated)>,
+) {
+    for (mut transform, interpolated) in object_query.iter_mut() {
+        transform.translation = interpolated.current;
+    }
+}
+
+pub fn store_simulated_translation(
+    mut commands: Commands,
+    mut object_query: Query<(Entity, &Transform, Option<&mut Interpolated>), With<Hitbox>>,
+) {
+    for (entity, transform, interpolated) in object_query.iter_mut() {
+        match interpolated {
+            Some(mut interpolated) => {
+                interpolated.previous = interpolated.current;
+                interpolated.current = transform.translation;
+            },
+            None => {
+                commands.entity(entity).insert(Interpolated {
+                    previous: transform.translation,
+                    current: transform.translation,
+                });
+            },
+        }
+    }
+}
+
+// draw objects between their last two ticks, so motion stays smooth whatever the frame rate
+pub fn interpolate_translation(
+    mut object_query: Query<(&mut Transform, &Interpolated)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let overstep = fixed_time.overstep_percentage();
+    for (mut transform, interpolated) in object_query.iter_mut() {
+        transform.translation = interpolated.previous.lerp(interpolated.current, overstep);
+    }
+}
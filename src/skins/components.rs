@@ -0,0 +1,8 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct SkinRoot;
+
+// a line of the skin screen, `None` is the way back to the menu
+#[derive(Component)]
+pub struct SkinEntry(pub Option<usize>);
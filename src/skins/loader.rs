@@ -0,0 +1,24 @@
+use bevy::utils::HashSet;
+
+use crate::data::RonAsset;
+
+use super::resources::SkinSet;
+
+impl RonAsset for SkinSet {
+    const NAME: &'static str = "skins";
+    const EXTENSIONS: &'static [&'static str] = &["skins.ron"];
+
+    fn validate(&mut self) -> Result<(), String> {
+        if self.skins.is_empty() {
+            return Err("there must be at least one skin".to_string());
+        }
+        // the skin worn is saved by id
+        let mut ids = HashSet::new();
+        for skin in self.skins.iter() {
+            if !ids.insert(skin.id.as_str()) {
+                return Err(format!("skin id {} is used more than once", skin.id));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+pub mod components;
+pub mod loader;
+pub mod resources;
+pub mod systems;
+
+use bevy::{
+    prelude::{Plugin, App, IntoSystemConfigs, OnEnter, OnExit, in_state},
+    app::{Startup, Update},
+};
+
+use crate::{data::RonAssetApp, systems::AppState};
+
+use self::{
+    resources::{SkinChoice, SkinSelection, SkinSet, Skins},
+    systems::{load_skin_set, update_skins, spawn_skin_screen, despawn_skin_screen, navigate_skins, update_skin_entries},
+};
+
+// sprite sets for the character defined in `assets/data/default.skins.ron` and picked on their own screen,
+// unlocked by a score or an achievement.
+// the skin worn feeds `CharacterTexture`, so the characters, the life icons and the ghost all wear it
+pub struct SkinPlug;
+
+impl Plugin for SkinPlug {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SkinChoice::load())
+            .init_ron_asset::<SkinSet>()
+            .init_resource::<Skins>()
+            .init_resource::<SkinSelection>()
+            .add_systems(Startup, load_skin_set)
+            .add_systems(Update, update_skins)
+            .add_systems(OnEnter(AppState::Skins), spawn_skin_screen)
+            .add_systems(OnExit(AppState::Skins), despawn_skin_screen)
+            .add_systems(Update, (navigate_skins, update_skin_entries).chain().run_if(in_state(AppState::Skins)));
+    }
+}
@@ -0,0 +1,173 @@
+use bevy::{prelude::*, reflect::TypePath};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievements::resources::AchievementProgress,
+    modes::resources::HighScores,
+    saves::{load_save, write_save},
+};
+
+const SKIN_SAVE: &str = "skin";
+
+const DEFAULT_SKIN: &str = "yellow";
+
+// what it takes to be allowed to wear a skin
+#[derive(Deserialize, Clone, Default)]
+pub enum Unlock {
+    #[default]
+    Free,
+    // best score of any run, in any game mode
+    Score(u32),
+    // id of an achievement of `assets/data/default.achievements.ron`
+    Achievement(String),
+}
+
+// a sprite set for the character, the walk animation alternates between head and tail
+#[derive(Deserialize, Clone)]
+pub struct Skin {
+    pub id: String,
+    pub name: String,
+    pub head: String,
+    pub tail: String,
+    #[serde(default)]
+    pub unlock: Unlock,
+}
+
+// the yellow alien, worn until the skin file is loaded or when it cannot be
+impl Default for Skin {
+    fn default() -> Self {
+        Skin {
+            id: DEFAULT_SKIN.to_string(),
+            name: DEFAULT_SKIN.to_string(),
+            head: "sprites/characters/alienYellow_walk2.png".to_string(),
+            tail: "sprites/characters/alienYellow_walk1.png".to_string(),
+            unlock: Unlock::Free,
+        }
+    }
+}
+
+impl Skin {
+    pub fn is_unlocked(&self, high_scores: &HighScores, achievement_progress: &AchievementProgress) -> bool {
+        match &self.unlock {
+            Unlock::Free => true,
+            Unlock::Score(score) => high_scores.best() >= *score,
+            Unlock::Achievement(id) => achievement_progress.unlocked.contains(id),
+        }
+    }
+
+    pub fn unlock_label(&self) -> String {
+        match &self.unlock {
+            Unlock::Free => String::new(),
+            Unlock::Score(score) => format!("score {score}"),
+            Unlock::Achievement(id) => format!("achievement {}", id.replace('_', " ")),
+        }
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize)]
+pub struct SkinSet {
+    pub skins: Vec<Skin>,
+}
+
+#[derive(Resource)]
+pub struct SkinSetHandle {
+    pub handle: Handle<SkinSet>,
+}
+
+// the skins on offer, copied from the skin file each time it is loaded
+#[derive(Resource)]
+pub struct Skins {
+    pub skins: Vec<Skin>,
+}
+
+impl Default for Skins {
+    fn default() -> Self {
+        Skins {
+            skins: vec![Skin::default()],
+        }
+    }
+}
+
+impl Skins {
+    pub fn index(&self, skin_choice: &SkinChoice) -> usize {
+        self.skins.iter().position(|skin| skin.id == skin_choice.id).unwrap_or_default()
+    }
+
+    // a skin removed from the file falls back to the first one
+    pub fn worn(&self, skin_choice: &SkinChoice) -> &Skin {
+        &self.skins[self.index(skin_choice)]
+    }
+}
+
+// the skin worn by every character, kept from one game to the next
+#[derive(Resource, Serialize, Deserialize)]
+pub struct SkinChoice {
+    pub id: String,
+}
+
+impl Default for SkinChoice {
+    fn default() -> Self {
+        SkinChoice {
+            id: DEFAULT_SKIN.to_string(),
+        }
+    }
+}
+
+impl SkinChoice {
+    pub fn load() -> Self {
+        load_save(SKIN_SAVE)
+    }
+
+    pub fn save(&self) {
+        write_save(SKIN_SAVE, self);
+    }
+}
+
+// index of the highlighted line, the line after the skins goes back to the menu
+#[derive(Resource, Default)]
+pub struct SkinSelection {
+    pub index: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        modes::resources::{GameMode, HighScore},
+        mutators::resources::Mutators,
+    };
+
+    use super::*;
+
+    fn skin(unlock: Unlock) -> Skin {
+        Skin { unlock, ..default() }
+    }
+
+    #[test]
+    fn free_skins_are_always_unlocked() {
+        assert!(skin(Unlock::Free).is_unlocked(&HighScores::default(), &AchievementProgress::default()));
+    }
+
+    #[test]
+    fn score_skins_unlock_at_the_best_score() {
+        let skin = skin(Unlock::Score(300));
+        let mut high_scores = HighScores::default();
+        let achievement_progress = AchievementProgress::default();
+        high_scores.insert(GameMode::Endless, Mutators::default(), HighScore { player: "player".to_string(), score: 299 });
+        assert!(!skin.is_unlocked(&high_scores, &achievement_progress));
+
+        high_scores.insert(GameMode::Hardcore, Mutators::default(), HighScore { player: "player".to_string(), score: 300 });
+        assert!(skin.is_unlocked(&high_scores, &achievement_progress));
+    }
+
+    #[test]
+    fn achievement_skins_unlock_with_their_achievement() {
+        let skin = skin(Unlock::Achievement("level_5".to_string()));
+        let high_scores = HighScores::default();
+        let mut achievement_progress = AchievementProgress::default();
+        achievement_progress.unlocked.insert("level_10".to_string());
+        assert!(!skin.is_unlocked(&high_scores, &achievement_progress));
+
+        achievement_progress.unlocked.insert("level_5".to_string());
+        assert!(skin.is_unlocked(&high_scores, &achievement_progress));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    achievements::resources::AchievementProgress,
+    modes::resources::HighScores,
+    systems::AppState,
+};
+
+use super::{
+    components::{SkinEntry, SkinRoot},
+    resources::{SkinChoice, SkinSelection, SkinSet, SkinSetHandle, Skins},
+};
+
+use bevy::prelude::*;
+
+const TITLE_FONT_SIZE: f32 = 40.;
+const ENTRY_FONT_SIZE: f32 = 28.;
+const SELECTED_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+const LOCKED_COLOR: Color = Color::GRAY;
+
+pub fn load_skin_set(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let handle: Handle<SkinSet> = asset_server.load("data/default.skins.ron");
+    commands.insert_resource(SkinSetHandle { handle });
+}
+
+pub fn update_skins(
+    mut asset_events: EventReader<AssetEvent<SkinSet>>,
+    mut skins: ResMut<Skins>,
+    skin_sets: Res<Assets<SkinSet>>,
+    skin_set_handle: Res<SkinSetHandle>,
+) {
+    let loaded = asset_events.read().any(|event| event.is_loaded_with_dependencies(&skin_set_handle.handle) || event.is_modified(&skin_set_handle.handle));
+    if !loaded {
+        return;
+    }
+    if let Some(skin_set) = skin_sets.get(&skin_set_handle.handle) {
+        skins.skins = skin_set.skins.clone();
+    }
+}
+
+fn entry_label(entry: Option<usize>, skins: &Skins, skin_choice: &SkinChoice, unlocked: bool) -> String {
+    let Some(index) = entry else {
+        return "back".to_string();
+    };
+    let skin = &skins.skins[index];
+    if !unlocked {
+        format!("{} (locked: {})", skin.name, skin.unlock_label())
+    } else if skins.index(skin_choice) == index {
+        format!("{} (worn)", skin.name)
+    } else {
+        skin.name.to_string()
+    }
+}
+
+fn entry_color(entry: Option<usize>, skins: &Skins, selected: usize, unlocked: bool) -> Color {
+    if entry.unwrap_or(skins.skins.len()) == selected {
+        SELECTED_COLOR
+    } else if unlocked {
+        Color::WHITE
+    } else {
+        LOCKED_COLOR
+    }
+}
+
+pub fn spawn_skin_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    skins: Res<Skins>,
+    skin_choice: Res<SkinChoice>,
+    high_scores: Res<HighScores>,
+    achievement_progress: Res<AchievementProgress>,
+    mut skin_selection: ResMut<SkinSelection>,
+) {
+    let font = asset_server.load("BungeeSpice-Regular.ttf");
+    skin_selection.index = skins.index(&skin_choice);
+
+    commands.spawn((
+        SkinRoot,
+        NodeBundle {
+            background_color: BackgroundColor(Color::BLACK.with_a(0.7)),
+            z_index: ZIndex::Global(i32::MAX),
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(12.),
+                ..default()
+            },
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            "skins",
+            TextStyle {
+                font_size: TITLE_FONT_SIZE,
+                color: Color::WHITE,
+                font: font.clone(),
+            },
+        ));
+        let entries = (0..skins.skins.len()).map(Some).chain([None]);
+        for entry in entries {
+            let unlocked = entry.is_none_or(|index| skins.skins[index].is_unlocked(&high_scores, &achievement_progress));
+            parent.spawn((
+                SkinEntry(entry),
+                TextBundle::from_section(
+                    entry_label(entry, &skins, &skin_choice, unlocked),
+                    TextStyle {
+                        font_size: ENTRY_FONT_SIZE,
+                        color: entry_color(entry, &skins, skin_selection.index, unlocked),
+                        font: font.clone(),
+                    },
+                ),
+            ));
+        }
+    });
+}
+
+pub fn despawn_skin_screen(mut commands: Commands, screen_query: Query<Entity, With<SkinRoot>>) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// up and down pick a line, enter wears the skin if it is unlocked, enter on back or backspace return to the menu
+pub fn navigate_skins(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut skin_selection: ResMut<SkinSelection>,
+    mut skin_choice: ResMut<SkinChoice>,
+    mut next_state: ResMut<NextState<AppState>>,
+    skins: Res<Skins>,
+    high_scores: Res<HighScores>,
+    achievement_progress: Res<AchievementProgress>,
+) {
+    let entries = skins.skins.len() + 1;
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        skin_selection.index = (skin_selection.index + entries - 1) % entries;
+    }
+    if keyboard_input.just_pressed(KeyCode::Down) {
+        skin_selection.index = (skin_selection.index + 1) % entries;
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        next_state.set(AppState::Menu);
+        return;
+    }
+
+    if !keyboard_input.just_pressed(KeyCode::Return) {
+        return;
+    }
+    match skins.skins.get(skin_selection.index) {
+        Some(skin) => {
+            if skin.is_unlocked(&high_scores, &achievement_progress) && skin_choice.id != skin.id {
+                skin_choice.id = skin.id.clone();
+                skin_choice.save();
+            }
+        },
+        None => next_state.set(AppState::Menu),
+    }
+}
+
+pub fn update_skin_entries(
+    skin_selection: Res<SkinSelection>,
+    skins: Res<Skins>,
+    skin_choice: Res<SkinChoice>,
+    high_scores: Res<HighScores>,
+    achievement_progress: Res<AchievementProgress>,
+    mut entry_query: Query<(&mut Text, &SkinEntry)>,
+) {
+    if !skin_selection.is_changed() && !skin_choice.is_changed() {
+        return;
+    }
+
+    for (mut text, entry) in entry_query.iter_mut() {
+        // the skin file may have been reloaded with fewer skins since the screen was built
+        if entry.0.is_some_and(|index| index >= skins.skins.len()) {
+            continue;
+        }
+        let unlocked = entry.0.is_none_or(|index| skins.skins[index].is_unlocked(&high_scores, &achievement_progress));
+        text.sections[0].value = entry_label(entry.0, &skins, &skin_choice, unlocked);
+        text.sections[0].style.color = entry_color(entry.0, &skins, skin_selection.index, unlocked);
+    }
+}
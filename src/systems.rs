@@ -1,14 +1,33 @@
+use std::str::FromStr;
+
 use bevy::app::AppExit;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
+use crate::camera::components::CameraController;
+
+// value following `flag` on the command line, e.g. `--seed 42`
+pub fn cli_arg<T: FromStr>(flag: &str) -> Option<T> {
+    let mut args = std::env::args().skip_while(|arg| arg != flag).skip(1);
+    args.next().and_then(|value| value.parse().ok())
+}
+
+// whether `flag` is on the command line, e.g. `--ghost`
+pub fn cli_flag(flag: &str) -> bool {
+    std::env::args().any(|arg| arg == flag)
+}
+
 pub fn spawn_camera(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
     let window = window_query.get_single().unwrap();
 
-    commands.spawn(Camera2dBundle {
-        transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 10.0),
-        ..default()
-    });
+    let home = Vec3::new(window.width() / 2.0, window.height() / 2.0, 10.0);
+    commands.spawn((
+        Camera2dBundle {
+            transform: Transform::from_translation(home),
+            ..default()
+        },
+        CameraController::new(home),
+    ));
 }
 
 pub fn exit_game(
@@ -20,11 +39,23 @@ pub fn exit_game(
     }
 }
 
-#[derive(Event)]
-pub struct GameOver {
-    pub score: u8,
+// the gameplay only starts once the match is set up in the menu,
+// and for an online match once the other player is connected in the lobby
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    Menu,
+    // toggling the mutators of the next run
+    Mutators,
+    // picking the skin of the characters
+    Skins,
+    Lobby,
+    Playing,
 }
 
+#[derive(Event)]
+pub struct GameOver;
+
 pub fn handle_game_over(
     mut game_over_event_reader: EventReader<GameOver>,
     mut app_exit_event_writer: EventWriter<AppExit>,
@@ -0,0 +1,25 @@
+use bevy::prelude::{Component, Vec2};
+
+// one layer of a theme, drawn as a single sprite repeating its texture.
+// a new theme fades in over the previous one, which fades out and goes away
+#[derive(Component)]
+pub struct ThemeLayerSprite {
+    pub theme: usize,
+    pub alpha: f32,
+    pub parallax: f32,
+    // from 0, invisible, to 1
+    pub fade: f32,
+    pub fading_out: bool,
+}
+
+// a sprite covering the window with its texture repeated every `tile_size`,
+// fitted once the texture is loaded and again when the window is resized
+#[derive(Component)]
+pub struct TiledLayer {
+    pub tile_size: Vec2,
+    pub fitted: bool,
+    // size of the image, known once fitted
+    pub texture_size: Vec2,
+    // how far the texture has scrolled, in tiles, wrapped around
+    pub scroll: f32,
+}
@@ -0,0 +1,24 @@
+use crate::data::RonAsset;
+
+use super::resources::ThemeSet;
+
+impl RonAsset for ThemeSet {
+    const NAME: &'static str = "themes";
+    const EXTENSIONS: &'static [&'static str] = &["themes.ron"];
+
+    fn validate(&mut self) -> Result<(), String> {
+        if self.themes.is_empty() {
+            return Err("theme set has no themes".to_string());
+        }
+        self.themes.sort_by_key(|theme| theme.from_level);
+        // a tile without size would never cover the playfield
+        for theme in self.themes.iter_mut() {
+            theme.tile_size = (theme.tile_size.0.max(1.), theme.tile_size.1.max(1.));
+            for layer in theme.layers.iter_mut() {
+                layer.tile_size = (layer.tile_size.0.max(1.), layer.tile_size.1.max(1.));
+                layer.alpha = layer.alpha.clamp(0., 1.);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+pub mod components;
+pub mod loader;
+pub mod resources;
+pub mod systems;
+
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, in_state}, app::{Startup, Update}};
+
+use crate::{data::RonAssetApp, systems::{cli_flag, AppState}};
+
+use self::{
+    resources::{ScrollSettings, ThemeSet},
+    systems::{
+        load_theme_set, switch_theme, fit_tiled_layers, fade_theme_layers, parallax_theme_layers, scrolling_enabled,
+        scroll_tiled_layers
+    },
+};
+
+// the background changes with the level: each level range has its own tiles, colour grade and parallax layers,
+// defined in `assets/data/default.themes.ron`. a new theme fades in over the previous one.
+// every layer is a single sprite with a repeating texture, whatever the size of the window.
+// with `--scroll` the layers also scroll during the run, faster at every level
+pub struct ThemePlug;
+
+impl Plugin for ThemePlug {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ScrollSettings { enabled: cli_flag("--scroll") })
+            .init_ron_asset::<ThemeSet>()
+            .add_systems(Startup, load_theme_set)
+            .add_systems(Update, (switch_theme, fit_tiled_layers, fade_theme_layers, parallax_theme_layers).chain())
+            .add_systems(Update, scroll_tiled_layers
+                .after(fit_tiled_layers)
+                .run_if(scrolling_enabled)
+                .run_if(in_state(AppState::Playing)));
+    }
+}
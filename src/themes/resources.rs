@@ -0,0 +1,73 @@
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+use crate::player::systems::CHARACTER_WIDTH;
+
+const DEFAULT_TILES: &str = "sprites/background/slimeBlock.png";
+// the slime block keeps its proportions at the width of the character
+const DEFAULT_TILE_SIZE: (f32, f32) = (CHARACTER_WIDTH, CHARACTER_WIDTH * 50. / 51.);
+
+// tiles drawn over the base tiles of a theme
+#[derive(Deserialize, Clone)]
+pub struct ThemeLayer {
+    pub texture: String,
+    pub tile_size: (f32, f32),
+    pub alpha: f32,
+    // how much the layer moves against the characters, 0 stays still
+    pub parallax: f32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub from_level: u32,
+    pub tiles: String,
+    pub tile_size: (f32, f32),
+    // tint of the whole theme, as rgb
+    pub grade: (f32, f32, f32),
+    #[serde(default)]
+    pub layers: Vec<ThemeLayer>,
+}
+
+// the plain slime cave, used until the theme file is loaded or when it cannot be
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name: "slime cave".to_string(),
+            from_level: 1,
+            tiles: DEFAULT_TILES.to_string(),
+            tile_size: DEFAULT_TILE_SIZE,
+            grade: (1., 1., 1.),
+            layers: Vec::new(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn grade(&self) -> Color {
+        Color::rgb(self.grade.0, self.grade.1, self.grade.2)
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize)]
+pub struct ThemeSet {
+    pub themes: Vec<Theme>,
+}
+
+impl ThemeSet {
+    // index of the theme of `level`, themes are sorted by the level they start from
+    pub fn index_for(&self, level: f32) -> usize {
+        self.themes.iter().rposition(|theme| theme.from_level as f32 <= level).unwrap_or_default()
+    }
+}
+
+// the background scrolls along with the ufos, as if the characters were running. enabled with `--scroll`
+#[derive(Resource, Default)]
+pub struct ScrollSettings {
+    pub enabled: bool,
+}
+
+#[derive(Resource)]
+pub struct ThemeSetHandle {
+    pub handle: Handle<ThemeSet>,
+}
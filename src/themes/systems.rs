@@ -0,0 +1,230 @@
+use crate::{
+    background::resources::{GameBonudary, GameMetadata, RunRules},
+    player::components::Character,
+};
+
+use super::{
+    components::{ThemeLayerSprite, TiledLayer},
+    resources::{ScrollSettings, Theme, ThemeSet, ThemeSetHandle},
+};
+
+use bevy::{
+    asset::LoadState,
+    prelude::*,
+    render::texture::{ImageAddressMode, ImageFilterMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor},
+    window::{PrimaryWindow, WindowResized},
+};
+
+// behind everything else, the layers of a theme are stacked from there
+const BACKGROUND_Z: f32 = -1.;
+const LAYER_Z_STEP: f32 = 0.1;
+// the incoming theme is drawn over the outgoing one
+const INCOMING_Z: f32 = 0.05;
+const TRANSITION_DURATION: f32 = 1.5;
+// pixels per second the ground scrolls by, it speeds up along with the ufos
+const SCROLL_SPEED: f32 = 40.;
+const EXTRA_SCROLL_SPEED_PER_LV: f32 = 8.;
+
+pub fn load_theme_set(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let handle: Handle<ThemeSet> = asset_server.load("data/default.themes.ron");
+    commands.insert_resource(ThemeSetHandle { handle });
+}
+
+// the texture of a layer wraps around, so a single sprite can repeat it over the whole window
+fn load_tiled_texture(asset_server: &AssetServer, path: &str) -> Handle<Image> {
+    asset_server.load_with_settings(path.to_string(), |settings: &mut ImageLoaderSettings| {
+        settings.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+            address_mode_u: ImageAddressMode::Repeat,
+            address_mode_v: ImageAddressMode::Repeat,
+            mag_filter: ImageFilterMode::Linear,
+            min_filter: ImageFilterMode::Linear,
+            ..default()
+        });
+    })
+}
+
+fn spawn_theme(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    theme: &Theme,
+    index: usize,
+    game_boundary: &GameBonudary,
+    fade: f32,
+) {
+    let grade = theme.grade();
+    let incoming = if fade < 1. { INCOMING_Z } else { 0. };
+    let base = (theme.tiles.as_str(), theme.tile_size, 1., 0.);
+    let layers = theme.layers.iter().map(|layer| (layer.texture.as_str(), layer.tile_size, layer.alpha, layer.parallax));
+
+    for (depth, (texture, tile_size, alpha, parallax)) in [base].into_iter().chain(layers).enumerate() {
+        let z = BACKGROUND_Z + depth as f32 * LAYER_Z_STEP + incoming;
+        commands.spawn((
+            ThemeLayerSprite {
+                theme: index,
+                alpha,
+                parallax,
+                fade,
+                fading_out: false,
+            },
+            TiledLayer {
+                tile_size: Vec2::new(tile_size.0, tile_size.1),
+                fitted: false,
+                texture_size: Vec2::ONE,
+                scroll: 0.,
+            },
+            SpriteBundle {
+                transform: Transform::from_xyz(game_boundary.x_mid, game_boundary.y_mid, z),
+                texture: load_tiled_texture(asset_server, texture),
+                sprite: Sprite {
+                    color: grade.with_a(alpha * fade),
+                    ..default()
+                },
+                // hidden until fitted, the texture would show once at its own size
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+        ));
+    }
+}
+
+// the sprite covers the window and one more tile around it, for the layers moving with parallax.
+// the texture is repeated by asking for more of it than the image holds
+pub fn fit_tiled_layers(
+    mut layer_query: Query<(&mut TiledLayer, &mut Sprite, &mut Visibility, &Handle<Image>)>,
+    mut resized_events: EventReader<WindowResized>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    images: Res<Assets<Image>>,
+) {
+    let resized = resized_events.read().count() > 0;
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    for (mut layer, mut sprite, mut visibility, texture) in layer_query.iter_mut() {
+        if layer.fitted && !resized {
+            continue;
+        }
+        let Some(image) = images.get(texture) else {
+            continue;
+        };
+        let tiles = ((Vec2::new(window.width(), window.height()) / layer.tile_size).ceil() + 2.).max(Vec2::ONE);
+        layer.texture_size = image.size_f32();
+        sprite.custom_size = Some(tiles * layer.tile_size);
+        sprite.rect = Some(scrolled_rect(tiles * layer.texture_size, layer.texture_size, layer.scroll));
+        *visibility = Visibility::Inherited;
+        layer.fitted = true;
+    }
+}
+
+// the part of the repeated texture to show once it scrolled by `scroll` tiles.
+// the texture repeats every tile, so only the fraction of a tile matters
+fn scrolled_rect(size: Vec2, texture_size: Vec2, scroll: f32) -> Rect {
+    let offset = Vec2::new(0., scroll.rem_euclid(1.) * texture_size.y);
+    Rect::from_corners(offset, offset + size)
+}
+
+pub fn scrolling_enabled(scroll_settings: Res<ScrollSettings>) -> bool {
+    scroll_settings.enabled
+}
+
+// the ground moves the way the ufos fall, the layers over it are closer and move faster
+pub fn scroll_tiled_layers(
+    mut layer_query: Query<(&mut TiledLayer, &mut Sprite, &ThemeLayerSprite)>,
+    game_metadata: Res<GameMetadata>,
+    run_rules: Res<RunRules>,
+    time: Res<Time>,
+) {
+    let speed = SCROLL_SPEED + game_metadata.level * EXTRA_SCROLL_SPEED_PER_LV;
+    for (mut layer, mut sprite, theme_layer) in layer_query.iter_mut() {
+        // the image is read from a lower offset to move its content down the screen
+        let distance = run_rules.fall_direction * speed * (1. + theme_layer.parallax) * time.delta_seconds();
+        layer.scroll = (layer.scroll + distance / layer.tile_size.y).rem_euclid(1.);
+        if let Some(rect) = sprite.rect {
+            sprite.rect = Some(scrolled_rect(rect.size(), layer.texture_size, layer.scroll));
+        }
+    }
+}
+
+// the theme follows the level, the one in place fades out when the next one is due
+pub fn switch_theme(
+    mut commands: Commands,
+    mut layer_query: Query<&mut ThemeLayerSprite>,
+    asset_server: Res<AssetServer>,
+    theme_sets: Res<Assets<ThemeSet>>,
+    theme_set_handle: Res<ThemeSetHandle>,
+    game_boundary: Res<GameBonudary>,
+    game_metadata: Res<GameMetadata>,
+) {
+    let fallback = Theme::default();
+    let (index, theme) = match theme_sets.get(&theme_set_handle.handle) {
+        Some(theme_set) => {
+            let index = theme_set.index_for(game_metadata.level);
+            (index, &theme_set.themes[index])
+        },
+        None if asset_server.load_state(theme_set_handle.handle.id()) == LoadState::Failed => (0, &fallback),
+        // wait for the theme file rather than flashing the fallback
+        None => return,
+    };
+
+    let mut shown = false;
+    let mut any = false;
+    for layer in layer_query.iter() {
+        any = true;
+        shown |= layer.theme == index && !layer.fading_out;
+    }
+    if shown {
+        return;
+    }
+
+    for mut layer in layer_query.iter_mut() {
+        layer.fading_out = true;
+    }
+    if any {
+        info!("background theme: {}", theme.name);
+    }
+    // the first theme is there from the start
+    let fade = if any { 0. } else { 1. };
+    spawn_theme(&mut commands, &asset_server, theme, index, &game_boundary, fade);
+}
+
+pub fn fade_theme_layers(
+    mut commands: Commands,
+    mut layer_query: Query<(Entity, &mut ThemeLayerSprite, &mut Transform, &mut Sprite)>,
+    time: Res<Time<Real>>,
+) {
+    let step = time.delta_seconds() / TRANSITION_DURATION;
+    for (entity, mut layer, mut transform, mut sprite) in layer_query.iter_mut() {
+        let fade = if layer.fading_out { layer.fade - step } else { layer.fade + step };
+        let fade = fade.clamp(0., 1.);
+        // a layer may start fading out before it was ever shown
+        if layer.fading_out && fade <= 0. {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        if fade == layer.fade {
+            continue;
+        }
+        layer.fade = fade;
+
+        if !layer.fading_out && fade >= 1. {
+            transform.translation.z -= INCOMING_Z;
+        }
+        sprite.color.set_a(layer.alpha * fade);
+    }
+}
+
+// layers further up move against the characters, as if they were further away
+pub fn parallax_theme_layers(
+    mut layer_query: Query<(&ThemeLayerSprite, &mut Transform)>,
+    character_query: Query<&Transform, (With<Character>, Without<ThemeLayerSprite>)>,
+    game_boundary: Res<GameBonudary>,
+) {
+    let count = character_query.iter().count();
+    let offset = match count {
+        0 => 0.,
+        _ => character_query.iter().map(|transform| transform.translation.x).sum::<f32>() / count as f32 - game_boundary.x_mid,
+    };
+    for (layer, mut transform) in layer_query.iter_mut() {
+        transform.translation.x = game_boundary.x_mid - offset * layer.parallax;
+    }
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::Component;
+
+// preview of the next drop, following the dropper along the top of the playfield
+#[derive(Component)]
+pub struct DropCursor;
+
+#[derive(Component)]
+pub struct VersusLabel;
@@ -0,0 +1,37 @@
+pub mod components;
+pub mod resources;
+pub mod systems;
+
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, OnEnter, resource_exists}, app::{FixedUpdate, Update}};
+
+use crate::{
+    background::systems::update_user_life,
+    collision::CollisionSet,
+    hud::HudSet,
+    simulation::{SimulationSet, rollback::RollbackApp},
+    systems::AppState,
+};
+
+use self::{
+    resources::{Dropper, VersusRounds},
+    systems::{
+        versus_match, start_versus, control_dropper, end_versus_round, update_drop_cursor, update_versus_label
+    },
+};
+
+// asymmetric match: one player dodges while the other one drops the ufo from the top,
+// then they swap. whoever survives longer wins
+pub struct VersusPlug;
+
+impl Plugin for VersusPlug {
+    fn build(&self, app: &mut App) {
+        app.rollback_resource::<VersusRounds>()
+            .rollback_resource::<Dropper>()
+            .add_systems(OnEnter(AppState::Playing), start_versus.after(HudSet::Spawn).run_if(versus_match))
+            .add_systems(FixedUpdate, (
+                control_dropper.after(SimulationSet::Begin).before(CollisionSet::Movement),
+                end_versus_round.after(update_user_life).before(SimulationSet::End),
+            ).run_if(resource_exists::<Dropper>()))
+            .add_systems(Update, (update_drop_cursor, update_versus_label).run_if(resource_exists::<Dropper>()));
+    }
+}
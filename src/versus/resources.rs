@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+pub const ROUNDS: usize = 2;
+// a round ends after this long even if the dropper holds on to its drops
+pub const ROUND_DURATION: f32 = 90.;
+const RESULT_DURATION: f32 = 4.;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum DropKind {
+    // a plain ghost
+    Ghost,
+    // a ghost falling twice as fast
+    Swift,
+    // three ghosts side by side
+    Cluster,
+}
+
+impl DropKind {
+    pub const ALL: [DropKind; 3] = [DropKind::Ghost, DropKind::Swift, DropKind::Cluster];
+
+    // drops available per round
+    pub fn budget(&self) -> u32 {
+        match self {
+            DropKind::Ghost => 40,
+            DropKind::Swift => 12,
+            DropKind::Cluster => 5,
+        }
+    }
+
+    pub fn cooldown(&self) -> f32 {
+        match self {
+            DropKind::Ghost => 0.5,
+            DropKind::Swift => 1.5,
+            DropKind::Cluster => 4.,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DropKind::Ghost => "ghost",
+            DropKind::Swift => "swift",
+            DropKind::Cluster => "cluster",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DropStock {
+    pub remaining: u32,
+    pub cooldown: Timer,
+}
+
+#[derive(Resource, Clone)]
+pub struct Dropper {
+    pub player: usize,
+    pub x: f32,
+    // index in `DropKind::ALL`
+    pub selected: usize,
+    pub stocks: Vec<DropStock>,
+}
+
+impl Dropper {
+    pub fn new(player: usize, x: f32) -> Self {
+        Dropper {
+            player,
+            x,
+            selected: 0,
+            stocks: DropKind::ALL.iter().map(|kind| {
+                let mut cooldown = Timer::from_seconds(kind.cooldown(), TimerMode::Once);
+                cooldown.tick(cooldown.duration());
+                DropStock {
+                    remaining: kind.budget(),
+                    cooldown,
+                }
+            }).collect(),
+        }
+    }
+
+    pub fn exhausted(&self) -> bool {
+        self.stocks.iter().all(|stock| stock.remaining == 0)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct RoundResult {
+    pub runner: usize,
+    pub survived: f32,
+    pub lifes: u8,
+}
+
+#[derive(Resource, Default, Clone)]
+pub struct VersusRounds {
+    pub runner: usize,
+    pub started_at: f32,
+    pub results: Vec<RoundResult>,
+    // set once every round is over, the result is shown for a while before the game ends
+    pub finished_at: Option<f32>,
+}
+
+impl VersusRounds {
+    pub fn round(&self) -> usize {
+        self.results.len() + 1
+    }
+
+    // time the runner has survived so far in the current round
+    pub fn elapsed(&self, now: f32) -> f32 {
+        (now - self.started_at).min(ROUND_DURATION)
+    }
+
+    pub fn showing_result_until(&self) -> Option<f32> {
+        self.finished_at.map(|finished_at| finished_at + RESULT_DURATION)
+    }
+
+    // the longest survival wins, then the most lifes left
+    pub fn winner(&self) -> Option<usize> {
+        let mut results: Vec<&RoundResult> = self.results.iter().collect();
+        results.sort_by(|a, b| b.survived.total_cmp(&a.survived).then(b.lifes.cmp(&a.lifes)));
+        match results.as_slice() {
+            [first, second, ..] if first.survived == second.survived && first.lifes == second.lifes => None,
+            [first, ..] => Some(first.runner),
+            [] => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounds(results: &[(usize, f32, u8)]) -> VersusRounds {
+        VersusRounds {
+            results: results.iter().map(|&(runner, survived, lifes)| RoundResult { runner, survived, lifes }).collect(),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn the_longest_survival_wins() {
+        assert_eq!(rounds(&[(0, 42., 0), (1, 57.5, 0)]).winner(), Some(1));
+        assert_eq!(rounds(&[(0, 60., 0), (1, 30., 3)]).winner(), Some(0));
+    }
+
+    #[test]
+    fn lifes_left_break_a_tie() {
+        assert_eq!(rounds(&[(0, 60., 1), (1, 60., 2)]).winner(), Some(1));
+    }
+
+    #[test]
+    fn a_full_tie_has_no_winner() {
+        assert_eq!(rounds(&[(0, 60., 2), (1, 60., 2)]).winner(), None);
+    }
+
+    #[test]
+    fn survival_is_counted_up_to_the_round_duration() {
+        let versus_rounds = VersusRounds { started_at: 10., ..default() };
+        assert_eq!(versus_rounds.elapsed(40.), 30.);
+        assert_eq!(versus_rounds.elapsed(10. + ROUND_DURATION * 2.), ROUND_DURATION);
+    }
+
+    #[test]
+    fn no_round_played_has_no_winner() {
+        assert_eq!(rounds(&[]).winner(), None);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{
+    background::resources::{GameBonudary, GameMetadata, LevelTimer, RunRules},
+    hud::components::HudSlot,
+    objects::{
+        components::{UfoType, Ufo},
+        resources::EnemyTexture,
+        systems::{pick_texture, spawn_ufo_at, UFO_HEIGHT, UFO_WIDTH},
+    },
+    player::{
+        components::Character,
+        resources::{CharacterTexture, MatchMode, MatchSettings},
+        systems::{spawn_character, CHARACTER_HEIGHT, CHARACTER_MOVEMENT_SPEED, PLAYER_TINTS},
+    },
+    shooter::components::Projectile,
+    simulation::resources::{GameRng, TickInput},
+    systems::GameOver,
+};
+
+use super::{
+    components::{DropCursor, VersusLabel},
+    resources::{DropKind, Dropper, RoundResult, VersusRounds, ROUNDS, ROUND_DURATION},
+};
+
+use bevy::prelude::*;
+
+const DROPPER_SPEED: f32 = CHARACTER_MOVEMENT_SPEED * 1.5;
+const SWIFT_SPEED_MULTIPLIER: f32 = 2.;
+const CURSOR_ALPHA: f32 = 0.5;
+
+pub fn versus_match(match_settings: Res<MatchSettings>) -> bool {
+    match_settings.mode == MatchMode::Versus
+}
+
+// the first player runs first, the second one drops
+pub fn start_versus(
+    mut commands: Commands,
+    game_boundary: Res<GameBonudary>,
+    asset_server: Res<AssetServer>,
+    texture: Res<EnemyTexture>,
+    slot_query: Query<(Entity, &HudSlot)>,
+) {
+    commands.insert_resource(VersusRounds::default());
+    commands.insert_resource(Dropper::new(1, game_boundary.x_mid));
+
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(game_boundary.x_mid, game_boundary.y_max - UFO_HEIGHT / 2., 1.),
+            texture: texture.handles[0].clone(),
+            sprite: Sprite {
+                color: PLAYER_TINTS[1].with_a(CURSOR_ALPHA),
+                ..default()
+            },
+            ..default()
+        },
+        DropCursor,
+    ));
+
+    let Some((slot, _)) = slot_query.iter().find(|(_, slot)| **slot == HudSlot::TopCenter) else {
+        return;
+    };
+    let text_versus = commands.spawn((
+        VersusLabel,
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 20.,
+                    color: Color::WHITE,
+                    font: asset_server.load("BungeeSpice-Regular.ttf"),
+                },
+            ).with_alignment(TextAlignment::Center),
+            ..default()
+        },
+    )).id();
+    commands.entity(slot).add_child(text_versus);
+}
+
+// left and right move the dropper, dash picks the next kind of drop and fire drops it
+#[allow(clippy::too_many_arguments)]
+pub fn control_dropper(
+    mut commands: Commands,
+    mut dropper: ResMut<Dropper>,
+    mut game_rng: ResMut<GameRng>,
+    versus_rounds: Res<VersusRounds>,
+    tick_input: Res<TickInput>,
+    texture: Res<EnemyTexture>,
+    game_boundary: Res<GameBonudary>,
+    game_metadata: Res<GameMetadata>,
+    run_rules: Res<RunRules>,
+    time: Res<Time>,
+) {
+    if versus_rounds.finished_at.is_some() {
+        return;
+    }
+
+    let input = tick_input.players[dropper.player];
+    dropper.x = (dropper.x + input.movement.x * DROPPER_SPEED * time.delta_seconds())
+        .clamp(game_boundary.x_min, game_boundary.x_max);
+    if input.dash {
+        dropper.selected = (dropper.selected + 1) % DropKind::ALL.len();
+    }
+
+    for stock in dropper.stocks.iter_mut() {
+        stock.cooldown.tick(time.delta());
+    }
+
+    let selected = dropper.selected;
+    let stock = &mut dropper.stocks[selected];
+    if !input.fire || !stock.cooldown.finished() || stock.remaining == 0 {
+        return;
+    }
+    stock.remaining -= 1;
+    stock.cooldown.reset();
+
+    let kind = DropKind::ALL[selected];
+    let offsets: &[f32] = match kind {
+        DropKind::Ghost | DropKind::Swift => &[0.],
+        DropKind::Cluster => &[-UFO_WIDTH, 0., UFO_WIDTH],
+    };
+    // the whole drop stays on the playfield, next to the edge it keeps its shape
+    let reach = offsets.iter().fold(0f32, |reach, offset| reach.max(offset.abs()));
+    let center = dropper.x.clamp(game_boundary.x_min + reach, game_boundary.x_max - reach);
+    for offset in offsets {
+        let x = center + offset;
+        let mut ufo = Ufo::steady(game_metadata.level, UFO_WIDTH, UFO_HEIGHT, 0, UfoType::Enemy);
+        if kind == DropKind::Swift {
+            ufo.fall_speed *= SWIFT_SPEED_MULTIPLIER;
+        }
+        let translation = Vec3::new(x, game_boundary.y_max, 0.);
+        spawn_ufo_at(&mut commands, pick_texture(&texture.handles, &mut game_rng.rng), translation, ufo, &run_rules, &game_boundary);
+    }
+}
+
+// a round is over once the runner is out of lifes, has outlived every drop or has run out the clock.
+// the roles swap for the next round, everyone starts from the same level
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn end_versus_round(
+    mut commands: Commands,
+    mut versus_rounds: ResMut<VersusRounds>,
+    mut dropper: ResMut<Dropper>,
+    mut game_metadata: ResMut<GameMetadata>,
+    mut level_timer: ResMut<LevelTimer>,
+    mut game_over_event: EventWriter<GameOver>,
+    character_query: Query<Entity, With<Character>>,
+    object_query: Query<Entity, Or<(With<Ufo>, With<Projectile>)>>,
+    character_texture: Res<CharacterTexture>,
+    game_boundary: Res<GameBonudary>,
+    run_rules: Res<RunRules>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds();
+    if let Some(until) = versus_rounds.showing_result_until() {
+        if now >= until {
+            game_over_event.send(GameOver);
+        }
+        return;
+    }
+
+    let lifes = game_metadata.players.get(versus_rounds.runner).map_or(0, |stats| stats.lifes);
+    let survived = versus_rounds.elapsed(now);
+    if lifes > 0 && survived < ROUND_DURATION && !(dropper.exhausted() && object_query.is_empty()) {
+        return;
+    }
+
+    let result = RoundResult {
+        runner: versus_rounds.runner,
+        survived,
+        lifes,
+    };
+    info!("player {} survived {:.1}s", result.runner + 1, result.survived);
+    versus_rounds.results.push(result);
+
+    for entity in character_query.iter().chain(object_query.iter()) {
+        commands.entity(entity).despawn();
+    }
+
+    if versus_rounds.results.len() >= ROUNDS {
+        versus_rounds.finished_at = Some(now);
+        return;
+    }
+
+    let runner = dropper.player;
+    *dropper = Dropper::new(versus_rounds.runner, game_boundary.x_mid);
+    versus_rounds.runner = runner;
+    versus_rounds.started_at = now;
+    game_metadata.level = run_rules.starting_level;
+    level_timer.timer.reset();
+
+    let translation = Vec3::new(game_boundary.x_mid, CHARACTER_HEIGHT * 1.5, 0.);
+    spawn_character(&mut commands, &character_texture, translation, runner, run_rules.character_hitbox_scale);
+}
+
+pub fn update_drop_cursor(
+    dropper: Res<Dropper>,
+    versus_rounds: Res<VersusRounds>,
+    mut cursor_query: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<DropCursor>>,
+) {
+    for (mut transform, mut sprite, mut visibility) in cursor_query.iter_mut() {
+        transform.translation.x = dropper.x;
+        sprite.color = PLAYER_TINTS[dropper.player].with_a(CURSOR_ALPHA);
+        let stock = &dropper.stocks[dropper.selected];
+        *visibility = if versus_rounds.finished_at.is_none() && stock.remaining > 0 {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+pub fn update_versus_label(
+    dropper: Res<Dropper>,
+    versus_rounds: Res<VersusRounds>,
+    time: Res<Time<Fixed>>,
+    mut versus_label_query: Query<&mut Text, With<VersusLabel>>,
+) {
+    let label = if versus_rounds.finished_at.is_some() {
+        match versus_rounds.winner() {
+            Some(winner) => format!("player {} wins", winner + 1),
+            None => "draw".to_string(),
+        }
+    } else {
+        let stocks: Vec<String> = DropKind::ALL.iter().zip(dropper.stocks.iter()).enumerate()
+            .map(|(index, (kind, stock))| {
+                let label = format!("{} {}", kind.label(), stock.remaining);
+                if index == dropper.selected { format!("[{label}]") } else { label }
+            })
+            .collect();
+        format!(
+            "round {}: player {} {:.1}s of {ROUND_DURATION:.0}s\n{}",
+            versus_rounds.round(),
+            versus_rounds.runner + 1,
+            versus_rounds.elapsed(time.elapsed_seconds()),
+            stocks.join(" "),
+        )
+    };
+
+    for mut text in &mut versus_label_query {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+
+// a formation is laid out on a grid of lanes (columns) and rows.
+// row 0 is the lowest row and reaches the character first
+#[derive(Deserialize, Clone)]
+pub enum Formation {
+    // a full row of ufo with a single gap, random if not given
+    Line { gap: Option<usize> },
+    // a v pointing down with its tip in the middle lane
+    V { depth: usize },
+    // a stack of ufo in one lane, random if not given
+    Column { lane: Option<usize>, length: usize },
+    // a few ufo dropped together on random lanes
+    Burst { count: usize },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Slot {
+    pub lane: usize,
+    pub row: usize,
+}
+
+fn random_lane(lanes: usize, rng: &mut impl Rng) -> usize {
+    rng.gen_range(0..lanes)
+}
+
+impl Formation {
+    pub fn slots(&self, lanes: usize, rng: &mut impl Rng) -> Vec<Slot> {
+        match *self {
+            Formation::Line { gap } => {
+                let gap = gap.map(|g| g.min(lanes - 1)).unwrap_or_else(|| random_lane(lanes, rng));
+                (0..lanes)
+                    .filter(|lane| *lane != gap)
+                    .map(|lane| Slot { lane, row: 0 })
+                    .collect()
+            },
+            Formation::V { depth } => {
+                let mid = lanes / 2;
+                let mut slots = vec![Slot { lane: mid, row: 0 }];
+                for row in 1..depth.min(mid + 1) {
+                    slots.push(Slot { lane: mid - row, row });
+                    if mid + row < lanes {
+                        slots.push(Slot { lane: mid + row, row });
+                    }
+                }
+                slots
+            },
+            Formation::Column { lane, length } => {
+                let lane = lane.map(|l| l.min(lanes - 1)).unwrap_or_else(|| random_lane(lanes, rng));
+                (0..length).map(|row| Slot { lane, row }).collect()
+            },
+            Formation::Burst { count } => {
+                let mut all_lanes: Vec<usize> = (0..lanes).collect();
+                all_lanes.shuffle(rng);
+                all_lanes
+                    .into_iter()
+                    .take(count)
+                    .map(|lane| Slot { lane, row: 0 })
+                    .collect()
+            },
+        }
+    }
+}
+
+// rows of a formation are too close for the character to change lane in between,
+// so the formation must keep one lane free along its whole height.
+// a lane `kept` from the formations before is cleared, otherwise one of the free lanes is picked,
+// or a random one is cleared if every lane is taken. returns the slots left and the free lane
+pub fn carve_safe_path(slots: Vec<Slot>, lanes: usize, kept: Option<usize>, rng: &mut impl Rng) -> (Vec<Slot>, usize) {
+    let free_lanes: Vec<usize> = (0..lanes).filter(|lane| slots.iter().all(|slot| slot.lane != *lane)).collect();
+    let safe_lane = match kept {
+        Some(lane) => lane,
+        None if !free_lanes.is_empty() => free_lanes[rng.gen_range(0..free_lanes.len())],
+        None => random_lane(lanes, rng),
+    };
+    (slots.into_iter().filter(|slot| slot.lane != safe_lane).collect(), safe_lane)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    const LANES: usize = 7;
+
+    fn free_lanes(slots: &[Slot]) -> Vec<usize> {
+        (0..LANES).filter(|lane| slots.iter().all(|slot| slot.lane != *lane)).collect()
+    }
+
+    #[test]
+    fn line_leaves_only_its_gap() {
+        let slots = Formation::Line { gap: Some(2) }.slots(LANES, &mut StdRng::seed_from_u64(0));
+        assert_eq!(slots.len(), LANES - 1);
+        assert_eq!(free_lanes(&slots), vec![2]);
+    }
+
+    #[test]
+    fn line_gap_is_kept_on_the_grid() {
+        let slots = Formation::Line { gap: Some(LANES + 3) }.slots(LANES, &mut StdRng::seed_from_u64(0));
+        assert_eq!(free_lanes(&slots), vec![LANES - 1]);
+    }
+
+    #[test]
+    fn v_points_down_from_the_middle_lane() {
+        let slots = Formation::V { depth: 3 }.slots(LANES, &mut StdRng::seed_from_u64(0));
+        assert!(slots.contains(&Slot { lane: 3, row: 0 }));
+        assert!(slots.contains(&Slot { lane: 1, row: 2 }));
+        assert!(slots.contains(&Slot { lane: 5, row: 2 }));
+        assert_eq!(slots.len(), 5);
+    }
+
+    #[test]
+    fn v_is_no_deeper_than_the_grid_is_wide() {
+        let slots = Formation::V { depth: 20 }.slots(LANES, &mut StdRng::seed_from_u64(0));
+        assert!(slots.iter().all(|slot| slot.lane < LANES));
+        assert_eq!(slots.len(), LANES);
+    }
+
+    #[test]
+    fn column_stacks_one_lane() {
+        let slots = Formation::Column { lane: Some(4), length: 3 }.slots(LANES, &mut StdRng::seed_from_u64(0));
+        assert_eq!(slots, vec![Slot { lane: 4, row: 0 }, Slot { lane: 4, row: 1 }, Slot { lane: 4, row: 2 }]);
+    }
+
+    #[test]
+    fn burst_drops_on_distinct_lanes() {
+        let slots = Formation::Burst { count: 4 }.slots(LANES, &mut StdRng::seed_from_u64(7));
+        assert_eq!(slots.len(), 4);
+        assert_eq!(free_lanes(&slots).len(), LANES - 4);
+    }
+
+    #[test]
+    fn safe_path_keeps_a_formation_with_a_free_lane() {
+        let slots = Formation::Line { gap: Some(0) }.slots(LANES, &mut StdRng::seed_from_u64(0));
+        let (carved, safe_lane) = carve_safe_path(slots.clone(), LANES, None, &mut StdRng::seed_from_u64(0));
+        assert_eq!(carved, slots);
+        assert_eq!(safe_lane, 0);
+    }
+
+    #[test]
+    fn safe_path_clears_the_kept_lane() {
+        let slots = Formation::Line { gap: Some(0) }.slots(LANES, &mut StdRng::seed_from_u64(0));
+        let (carved, safe_lane) = carve_safe_path(slots, LANES, Some(4), &mut StdRng::seed_from_u64(0));
+        assert_eq!(safe_lane, 4);
+        assert_eq!(free_lanes(&carved), vec![0, 4]);
+    }
+
+    #[test]
+    fn safe_path_clears_a_lane_when_all_are_taken() {
+        let mut slots = Formation::Burst { count: LANES }.slots(LANES, &mut StdRng::seed_from_u64(1));
+        slots.extend(Formation::Column { lane: Some(3), length: 2 }.slots(LANES, &mut StdRng::seed_from_u64(1)));
+        assert!(free_lanes(&slots).is_empty());
+
+        let (carved, safe_lane) = carve_safe_path(slots, LANES, None, &mut StdRng::seed_from_u64(1));
+        assert_eq!(free_lanes(&carved), vec![safe_lane]);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::data::RonAsset;
+
+use super::resources::WaveSet;
+
+impl RonAsset for WaveSet {
+    const NAME: &'static str = "wave set";
+    const EXTENSIONS: &'static [&'static str] = &["waves.ron"];
+
+    fn validate(&mut self) -> Result<(), String> {
+        // a wave without formation would never end its spawning phase
+        self.waves.retain(|wave| !wave.formations.is_empty());
+        if self.waves.is_empty() {
+            return Err("no wave has formations".to_string());
+        }
+        self.repeat_from = self.repeat_from.min(self.waves.len() - 1);
+        for wave in self.waves.iter_mut() {
+            wave.rest = wave.rest.max(0.);
+            wave.interval = wave.interval.max(0.);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+pub mod formations;
+pub mod loader;
+pub mod resources;
+pub mod systems;
+
+use bevy::{prelude::{Plugin, App, IntoSystemConfigs, not}, app::{Startup, FixedUpdate}};
+
+use crate::{
+    boss::systems::no_boss, collision::stress::stress_test_running, data::RonAssetApp, simulation::rollback::RollbackApp,
+    versus::systems::versus_match
+};
+
+use self::{
+    resources::{WaveDirector, WaveSet},
+    systems::{load_wave_set, run_wave_director},
+};
+
+pub struct WavePlug;
+
+impl Plugin for WavePlug {
+    fn build(&self, app: &mut App) {
+        app.init_ron_asset::<WaveSet>()
+            .init_resource::<WaveDirector>()
+            .rollback_resource::<WaveDirector>()
+            .add_systems(Startup, load_wave_set)
+            .add_systems(FixedUpdate, run_wave_director.run_if(no_boss).run_if(not(versus_match)).run_if(not(stress_test_running)));
+    }
+}
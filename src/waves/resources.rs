@@ -0,0 +1,55 @@
+use bevy::{prelude::*, reflect::TypePath};
+use serde::Deserialize;
+
+use super::formations::Formation;
+
+const FIRST_WAVE_DELAY: f32 = 2.0;
+
+#[derive(Deserialize)]
+pub struct WaveDefinition {
+    // seconds of calm before the wave starts
+    pub rest: f32,
+    // seconds between two formations of the wave
+    pub interval: f32,
+    pub formations: Vec<Formation>,
+}
+
+#[derive(Asset, TypePath, Deserialize)]
+pub struct WaveSet {
+    pub waves: Vec<WaveDefinition>,
+    // once the last wave is done, loop back to this wave
+    #[serde(default)]
+    pub repeat_from: usize,
+}
+
+#[derive(Resource)]
+pub struct WaveSetHandle {
+    pub handle: Handle<WaveSet>,
+}
+
+// the free lane of the last formations, kept free in the next ones until the character
+// could have reached any other lane
+#[derive(Clone, Copy)]
+pub struct SafeLane {
+    pub lane: usize,
+    pub until: f32,
+}
+
+#[derive(Resource, Clone)]
+pub struct WaveDirector {
+    pub wave: usize,
+    pub formation: usize,
+    pub timer: Timer,
+    pub safe_lane: Option<SafeLane>,
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        WaveDirector {
+            wave: 0,
+            formation: 0,
+            timer: Timer::from_seconds(FIRST_WAVE_DELAY, TimerMode::Once),
+            safe_lane: None,
+        }
+    }
+}
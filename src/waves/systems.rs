@@ -0,0 +1,116 @@
+use crate::{
+    background::{
+        resources::{GameBonudary, GameMetadata, RunRules},
+        systems::WINDOW_WIDTH,
+    },
+    objects::{
+        components::{UfoType, Ufo},
+        resources::EnemyTexture,
+        systems::{pick_texture, spawn_ufo_at, UFO_HEIGHT, UFO_WIDTH},
+    },
+    player::systems::{CHARACTER_HEIGHT, CHARACTER_MOVEMENT_SPEED, CHARACTER_WIDTH},
+    simulation::resources::{GameRng, PendingAssets},
+};
+
+use super::{
+    formations::carve_safe_path,
+    resources::{SafeLane, WaveDirector, WaveSet, WaveSetHandle},
+};
+
+use bevy::{asset::LoadState, prelude::*};
+
+// lanes are as wide as the character, so a single free lane is always enough to pass through
+const LANES: usize = (WINDOW_WIDTH / CHARACTER_WIDTH) as usize;
+const LANE_WIDTH: f32 = WINDOW_WIDTH / LANES as f32;
+const ROW_SPACING: f32 = UFO_HEIGHT * 1.5;
+
+pub fn load_wave_set(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut pending_assets: ResMut<PendingAssets>,
+) {
+    let handle: Handle<WaveSet> = asset_server.load("data/default.waves.ron");
+    pending_assets.handles.push(handle.clone().untyped());
+    commands.insert_resource(WaveSetHandle { handle });
+}
+
+// fall back to the plain enemy timer when the wave file cannot be used
+pub fn waves_unavailable(
+    asset_server: Res<AssetServer>,
+    wave_set_handle: Res<WaveSetHandle>,
+) -> bool {
+    asset_server.load_state(wave_set_handle.handle.id()) == LoadState::Failed
+}
+
+// seconds the free lane of a formation stays free in the formations after it.
+// covers the formation passing the character, then the character crossing the whole play area
+fn safe_delay(rows: usize, fall_speed: f32) -> f32 {
+    let formation_span = (rows as f32 - 1.) * ROW_SPACING + UFO_HEIGHT + CHARACTER_HEIGHT;
+    formation_span / fall_speed + WINDOW_WIDTH / CHARACTER_MOVEMENT_SPEED
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_wave_director(
+    mut commands: Commands,
+    mut director: ResMut<WaveDirector>,
+    wave_sets: Res<Assets<WaveSet>>,
+    wave_set_handle: Res<WaveSetHandle>,
+    texture: Res<EnemyTexture>,
+    game_boundary: Res<GameBonudary>,
+    game_metadata: Res<GameMetadata>,
+    run_rules: Res<RunRules>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    let Some(wave_set) = wave_sets.get(&wave_set_handle.handle) else {
+        return;
+    };
+
+    director.timer.tick(time.delta());
+    if !director.timer.finished() {
+        return;
+    }
+
+    // the wave set may have been hot reloaded with less waves or formations
+    let Some(wave) = wave_set.waves.get(director.wave) else {
+        *director = WaveDirector::default();
+        return;
+    };
+    let Some(formation) = wave.formations.get(director.formation) else {
+        *director = WaveDirector::default();
+        return;
+    };
+
+    let now = time.elapsed_seconds();
+    let kept = director.safe_lane.filter(|safe_lane| now < safe_lane.until).map(|safe_lane| safe_lane.lane);
+    let rng = &mut game_rng.rng;
+    let (slots, lane) = carve_safe_path(formation.slots(LANES, rng), LANES, kept, rng);
+    let rows = slots.iter().map(|slot| slot.row + 1).max().unwrap_or(1);
+    for slot in slots {
+        let translation = Vec3::new(
+            game_boundary.x_min + LANE_WIDTH * (slot.lane as f32 + 0.5),
+            game_boundary.y_max + ROW_SPACING * slot.row as f32,
+            0.,
+        );
+        let ufo = Ufo::steady(game_metadata.level, UFO_WIDTH, UFO_HEIGHT, 0, UfoType::Enemy);
+        spawn_ufo_at(&mut commands, pick_texture(&texture.handles, rng), translation, ufo, &run_rules, &game_boundary);
+    }
+
+    director.safe_lane = Some(SafeLane {
+        lane,
+        until: now + safe_delay(rows, Ufo::base_speed(game_metadata.level)),
+    });
+    director.formation += 1;
+    if director.formation < wave.formations.len() {
+        director.timer = Timer::from_seconds(wave.interval, TimerMode::Once);
+    } else {
+        director.wave = if director.wave + 1 < wave_set.waves.len() {
+            director.wave + 1
+        } else {
+            wave_set.repeat_from
+        };
+        director.formation = 0;
+        let rest = wave_set.waves[director.wave].rest;
+        director.timer = Timer::from_seconds(rest, TimerMode::Once);
+    }
+}